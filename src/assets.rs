@@ -1,4 +1,4 @@
-use crate::*;
+use bevy::prelude::*;
 
 pub struct AssetPath;
 impl AssetPath {
@@ -26,7 +26,7 @@ pub trait BevypunkColorPalette {
     const BEVYPUNK_BLUE: Color;
 }
 impl BevypunkColorPalette for Color {
-    const BEVYPUNK_RED: Color = Color::srgba(255./255., 98./255., 81./255., 1.0);
+    const BEVYPUNK_RED: Color = Color::srgba(1.0, 98./255., 81./255., 1.0);
     const BEVYPUNK_RED_DIM: Color = Color::srgba(172./255., 64./255., 63./255., 1.0);
     const BEVYPUNK_YELLOW: Color = Color::linear_rgba(252./255., 226./255., 8./255., 1.0);
     const BEVYPUNK_BLUE: Color = Color::srgba(8./255., 226./255., 252./255., 1.0);
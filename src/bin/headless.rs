@@ -0,0 +1,7 @@
+use bevy::app::AppExit;
+
+/// Runs a session without a window or GPU and prints a JSON summary, see `bevy_game::headless`.
+fn main() -> AppExit {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    bevy_game::headless::run_from_args(&args)
+}
@@ -0,0 +1,177 @@
+use std::collections::{HashMap, HashSet};
+
+use bevy::{math::{IVec2, Rect, Vec2}, prelude::{Entity, Resource}};
+use serde::Deserialize;
+
+use super::paths::LanePath;
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct BuildGridDefinition {
+    pub cell_size: f32,
+    pub origin: Vec2,
+    pub columns: i32,
+    pub rows: i32,
+    pub path_width: f32,
+    #[serde(default)]
+    pub blocked: Vec<IVec2>
+}
+
+#[derive(Resource, Default)]
+pub struct BuildGrid {
+    pub cell_size: f32,
+    pub origin: Vec2,
+    pub columns: i32,
+    pub rows: i32,
+    pub blocked: HashSet<IVec2>,
+    pub occupied: HashMap<IVec2, Entity>
+}
+
+impl BuildGrid {
+    pub fn new(definition: &BuildGridDefinition, lanes: &[LanePath]) -> Self {
+        let mut grid = Self {
+            cell_size: definition.cell_size,
+            origin: definition.origin,
+            columns: definition.columns,
+            rows: definition.rows,
+            blocked: definition.blocked.iter().copied().collect(),
+            occupied: HashMap::new()
+        };
+
+        let clearance = definition.path_width / 2. + definition.cell_size / 2.;
+        for x in 0..grid.columns {
+            for y in 0..grid.rows {
+                let cell = IVec2::new(x, y);
+                let center = grid.cell_center(cell);
+
+                let on_path = lanes.iter()
+                    .flat_map(|lane| lane.points.windows(2))
+                    .any(|segment| distance_to_segment(center, segment[0], segment[1]) < clearance);
+
+                if on_path {
+                    grid.blocked.insert(cell);
+                }
+            }
+        }
+
+        grid
+    }
+
+    pub fn cell_at(&self, pos: Vec2) -> Option<IVec2> {
+        if self.cell_size <= 0. {
+            return None;
+        }
+
+        let cell = ((pos - self.origin) / self.cell_size).floor().as_ivec2();
+        self.contains(cell).then_some(cell)
+    }
+
+    pub fn contains(&self, cell: IVec2) -> bool {
+        cell.x >= 0 && cell.y >= 0 && cell.x < self.columns && cell.y < self.rows
+    }
+
+    pub fn bounds(&self) -> Rect {
+        Rect::from_corners(self.origin, self.origin + IVec2::new(self.columns, self.rows).as_vec2() * self.cell_size)
+    }
+
+    pub fn cell_center(&self, cell: IVec2) -> Vec2 {
+        self.origin + (cell.as_vec2() + Vec2::splat(0.5)) * self.cell_size
+    }
+
+    pub fn is_buildable(&self, cell: IVec2) -> bool {
+        self.contains(cell) && !self.blocked.contains(&cell) && !self.occupied.contains_key(&cell)
+    }
+
+    pub fn occupy(&mut self, cell: IVec2, entity: Entity) {
+        self.occupied.insert(cell, entity);
+    }
+
+    pub fn cell_of(&self, entity: Entity) -> Option<IVec2> {
+        self.occupied.iter().find(|(_, occupant)| **occupant == entity).map(|(cell, _)| *cell)
+    }
+
+    pub fn free(&mut self, entity: Entity) {
+        self.occupied.retain(|_, occupant| *occupant != entity);
+    }
+}
+
+fn distance_to_segment(point: Vec2, a: Vec2, b: Vec2) -> f32 {
+    let ab = b - a;
+    let length_squared = ab.length_squared();
+    if length_squared <= f32::EPSILON {
+        return point.distance(a);
+    }
+
+    let t = ((point - a).dot(ab) / length_squared).clamp(0., 1.);
+    point.distance(a + ab * t)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 10x10 cells of 10 units, with a lane running through the middle row.
+    fn grid() -> BuildGrid {
+        let definition = BuildGridDefinition {
+            cell_size: 10.,
+            origin: Vec2::ZERO,
+            columns: 10,
+            rows: 10,
+            path_width: 12.,
+            blocked: vec![IVec2::new(0, 0)]
+        };
+        let lane = LanePath::new("lane".into(), vec![Vec2::new(0., 55.), Vec2::new(100., 55.)]);
+
+        BuildGrid::new(&definition, &[lane])
+    }
+
+    #[test]
+    fn lanes_block_the_cells_they_pass() {
+        let grid = grid();
+
+        for x in 0..10 {
+            assert!(!grid.is_buildable(IVec2::new(x, 5)), "cell ({x}, 5) is on the lane");
+        }
+
+        assert!(grid.is_buildable(IVec2::new(3, 3)));
+        assert!(grid.is_buildable(IVec2::new(3, 7)));
+    }
+
+    #[test]
+    fn clearance_covers_the_path_width() {
+        let grid = grid();
+
+        // Centres 10 away from the lane are closer than half a cell plus half the path
+        assert!(!grid.is_buildable(IVec2::new(3, 4)));
+        assert!(!grid.is_buildable(IVec2::new(3, 6)));
+    }
+
+    #[test]
+    fn listed_cells_are_blocked() {
+        assert!(!grid().is_buildable(IVec2::new(0, 0)));
+    }
+
+    #[test]
+    fn occupied_cells_are_freed_by_their_turret() {
+        let mut grid = grid();
+        let turret = Entity::from_raw(1);
+        let cell = IVec2::new(2, 2);
+
+        grid.occupy(cell, turret);
+        assert!(!grid.is_buildable(cell));
+        assert_eq!(grid.cell_of(turret), Some(cell));
+
+        grid.free(turret);
+        assert!(grid.is_buildable(cell));
+        assert_eq!(grid.cell_of(turret), None);
+    }
+
+    #[test]
+    fn positions_outside_the_grid_have_no_cell() {
+        let grid = grid();
+
+        assert_eq!(grid.cell_at(Vec2::new(25., 35.)), Some(IVec2::new(2, 3)));
+        assert_eq!(grid.cell_at(Vec2::new(-1., 5.)), None);
+        assert_eq!(grid.cell_at(Vec2::new(100., 5.)), None);
+        assert!(!grid.is_buildable(IVec2::new(10, 0)));
+    }
+}
@@ -0,0 +1,47 @@
+use std::collections::HashMap;
+
+use bevy::prelude::{Component, Entity, Event};
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumIter};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Display, EnumIter, Serialize, Deserialize)]
+pub enum DamageType {
+    #[default]
+    Kinetic,
+    Energy,
+    Fire,
+    Cold,
+    Acid,
+    Electric
+}
+
+#[derive(Component)]
+pub struct DamageKind(pub DamageType);
+
+/// Damage multipliers per type, values below 1 are resistances and above 1 weaknesses.
+#[derive(Component, Default, Clone, Debug, Deserialize)]
+#[serde(transparent)]
+pub struct Resistances(pub HashMap<DamageType, f32>);
+
+impl Resistances {
+    pub fn multiplier(&self, damage_type: DamageType) -> f32 {
+        self.0.get(&damage_type).copied().unwrap_or(1.)
+    }
+}
+
+#[derive(Event)]
+pub struct DamageEvent {
+    pub source: Option<Entity>,
+    pub target: Entity,
+    pub amount: f32,
+    pub damage_type: DamageType
+}
+
+#[derive(Component)]
+pub struct FiredBy(pub Entity);
+
+#[derive(Component)]
+pub struct DamageTarget(pub Entity);
+
+#[derive(Component)]
+pub struct KilledBy(pub Option<Entity>);
@@ -0,0 +1,85 @@
+use bevy::{math::Vec2, prelude::{Component, Entity, Event, Resource}};
+
+use crate::TurretType;
+
+use super::enemies::EnemyType;
+
+#[derive(Resource)]
+pub struct EconomySettings {
+    pub starting_money: u32,
+    /// Fraction of everything invested into a turret that is returned when it is sold.
+    pub sell_refund: f32
+}
+
+impl Default for EconomySettings {
+    fn default() -> Self {
+        Self {
+            starting_money: 250,
+            sell_refund: 0.7
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct Wallet {
+    pub money: u32
+}
+
+impl Wallet {
+    pub fn new(money: u32) -> Self {
+        Self { money }
+    }
+
+    pub fn can_afford(&self, amount: u32) -> bool {
+        self.money >= amount
+    }
+
+    pub fn try_spend(&mut self, amount: u32) -> bool {
+        if !self.can_afford(amount) {
+            return false;
+        }
+
+        self.money -= amount;
+        true
+    }
+
+    pub fn earn(&mut self, amount: u32) {
+        self.money = self.money.saturating_add(amount);
+    }
+}
+
+#[derive(Component, Default)]
+pub struct Invested(pub u32);
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TransactionKind {
+    Build(TurretType),
+    Upgrade(TurretType),
+    Sell(TurretType),
+    Bounty(EnemyType),
+    EarlyCallBonus
+}
+
+#[derive(Event)]
+pub struct Transaction {
+    pub kind: TransactionKind,
+    pub amount: i64,
+    pub balance: u32
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RejectReason {
+    NotEnoughMoney { cost: u32, available: u32 },
+    InvalidCell,
+    Unavailable
+}
+
+#[derive(Event)]
+pub struct BuildRejected {
+    pub turret_type: TurretType,
+    pub reason: RejectReason,
+    pub pos: Vec2
+}
+
+#[derive(Event)]
+pub struct SellTurret(pub Entity);
@@ -0,0 +1,123 @@
+use std::collections::HashMap;
+
+use bevy::{asset::Asset, color::{Alpha, Color}, math::Vec3, prelude::{Component, Entity, Event, Handle, Resource}, reflect::TypePath};
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumIter};
+
+use super::damage::Resistances;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Display, EnumIter, Serialize, Deserialize)]
+pub enum EnemyType {
+    #[default]
+    Drone,
+    Crawler,
+    Runner,
+    Juggernaut,
+    Phantom
+}
+
+#[derive(Asset, TypePath, Deserialize, Debug)]
+#[serde(transparent)]
+pub struct EnemyDefinitions(pub HashMap<EnemyType, EnemyDefinition>);
+
+impl EnemyDefinitions {
+    pub fn get(&self, enemy_type: EnemyType) -> Option<&EnemyDefinition> {
+        self.0.get(&enemy_type)
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct EnemyDefinition {
+    pub health: f32,
+    pub armor: f32,
+    pub speed: f32,
+    pub bounty: u32,
+    pub radius: f32,
+    pub color: (f32, f32, f32),
+    #[serde(default)]
+    pub resistances: Resistances,
+    #[serde(default)]
+    pub stealth: bool,
+    /// Lives lost when this enemy reaches the exit.
+    #[serde(default = "default_life_cost")]
+    pub life_cost: u32
+}
+
+fn default_life_cost() -> u32 {
+    1
+}
+
+pub const STEALTH_ALPHA: f32 = 0.3;
+
+impl EnemyDefinition {
+    pub fn color(&self) -> Color {
+        let color = Color::srgb(self.color.0, self.color.1, self.color.2);
+
+        if self.stealth {
+            color.with_alpha(STEALTH_ALPHA)
+        } else {
+            color
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct CurrentEnemyDefinitions(pub Handle<EnemyDefinitions>);
+
+#[derive(Component)]
+pub struct Enemy(pub EnemyType);
+
+#[derive(Component)]
+pub struct Health {
+    pub current: f32,
+    pub max: f32
+}
+
+impl Health {
+    pub fn new(max: f32) -> Self {
+        Self { current: max, max }
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.current <= 0.
+    }
+}
+
+#[derive(Component)]
+pub struct Armor(pub f32);
+
+#[derive(Component)]
+pub struct MovementSpeed(pub f32);
+
+#[derive(Component)]
+pub struct Bounty(pub u32);
+
+#[derive(Component)]
+pub struct HitRadius(pub f32);
+
+#[derive(Component)]
+pub struct Stealth;
+
+#[derive(Component)]
+pub struct Revealed;
+
+#[derive(Event)]
+pub struct SpawnEnemy {
+    pub enemy_type: EnemyType,
+    pub lane: usize
+}
+
+#[derive(Event)]
+pub struct EnemyKilled {
+    pub entity: Entity,
+    pub killer: Option<Entity>,
+    pub enemy_type: EnemyType,
+    pub pos: Vec3,
+    pub bounty: u32
+}
+
+#[derive(Event)]
+pub struct EnemyLeaked {
+    pub entity: Entity,
+    pub enemy_type: EnemyType
+}
@@ -1 +1,14 @@
-pub mod turrets;
\ No newline at end of file
+pub mod turrets;
+pub mod enemies;
+pub mod paths;
+pub mod waves;
+pub mod damage;
+pub mod status_effects;
+pub mod build;
+pub mod economy;
+pub mod upgrades;
+pub mod session;
+pub mod save;
+pub mod simulation;
+pub mod replay;
+pub mod spatial;
@@ -0,0 +1,204 @@
+use bevy::{asset::Asset, math::{Rect, Vec2}, prelude::{Component, Handle, Resource}, reflect::TypePath};
+use serde::Deserialize;
+
+use super::build::BuildGridDefinition;
+
+#[derive(Asset, TypePath, Deserialize, Debug)]
+pub struct GameMap {
+    pub lanes: Vec<Lane>,
+    pub build_grid: BuildGridDefinition
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct Lane {
+    pub name: String,
+    pub waypoints: Vec<Vec2>,
+    #[serde(default)]
+    pub merges_into: Option<LaneMerge>
+}
+
+/// Continues the lane along another lane, starting from one of its waypoints.
+#[derive(Deserialize, Debug, Clone)]
+pub struct LaneMerge {
+    pub lane: String,
+    pub waypoint: usize
+}
+
+#[derive(Resource)]
+pub struct CurrentMap(pub Handle<GameMap>);
+
+/// Area the map covers, its build grid and every lane, projectiles leaving it are despawned.
+#[derive(Resource, Default)]
+pub struct ArenaBounds(pub Rect);
+
+#[derive(Resource, Default)]
+pub struct MapPaths {
+    pub lanes: Vec<LanePath>
+}
+
+impl MapPaths {
+    const MAX_MERGE_DEPTH: usize = 8;
+
+    pub fn from_map(map: &GameMap) -> Self {
+        let lanes = map.lanes.iter()
+            .map(|lane| LanePath::new(lane.name.clone(), Self::resolve_waypoints(map, lane, 0)))
+            .collect();
+
+        Self { lanes }
+    }
+
+    fn resolve_waypoints(map: &GameMap, lane: &Lane, depth: usize) -> Vec<Vec2> {
+        let mut points = lane.waypoints.clone();
+
+        let Some(merge) = &lane.merges_into else {
+            return points;
+        };
+
+        if depth >= Self::MAX_MERGE_DEPTH {
+            return points;
+        }
+
+        if let Some(target) = map.lanes.iter().find(|other| other.name == merge.lane) {
+            let tail = Self::resolve_waypoints(map, target, depth + 1);
+            points.extend(tail.into_iter().skip(merge.waypoint));
+        }
+
+        points
+    }
+
+    pub fn lane_index(&self, name: &str) -> Option<usize> {
+        self.lanes.iter().position(|lane| lane.name == name)
+    }
+
+    pub fn get(&self, lane: usize) -> Option<&LanePath> {
+        self.lanes.get(lane)
+    }
+}
+
+pub struct LanePath {
+    pub name: String,
+    pub points: Vec<Vec2>,
+    cumulative_lengths: Vec<f32>
+}
+
+impl LanePath {
+    pub fn new(name: String, points: Vec<Vec2>) -> Self {
+        let mut cumulative_lengths = Vec::with_capacity(points.len());
+        let mut total = 0.;
+
+        for (i, point) in points.iter().enumerate() {
+            if i > 0 {
+                total += points[i - 1].distance(*point);
+            }
+            cumulative_lengths.push(total);
+        }
+
+        Self { name, points, cumulative_lengths }
+    }
+
+    pub fn length(&self) -> f32 {
+        self.cumulative_lengths.last().copied().unwrap_or(0.)
+    }
+
+    pub fn start(&self) -> Vec2 {
+        self.points.first().copied().unwrap_or(Vec2::ZERO)
+    }
+
+    pub fn end(&self) -> Vec2 {
+        self.points.last().copied().unwrap_or(Vec2::ZERO)
+    }
+
+    /// Position on the lane after travelling `distance` from its start.
+    pub fn sample(&self, distance: f32) -> Vec2 {
+        if distance <= 0. {
+            return self.start();
+        }
+
+        let segment = self.cumulative_lengths.partition_point(|length| *length <= distance);
+        if segment == 0 || segment >= self.points.len() {
+            return self.end();
+        }
+
+        let from = self.points[segment - 1];
+        let to = self.points[segment];
+        let segment_start = self.cumulative_lengths[segment - 1];
+        let segment_length = self.cumulative_lengths[segment] - segment_start;
+
+        if segment_length <= f32::EPSILON {
+            return to;
+        }
+
+        from.lerp(to, (distance - segment_start) / segment_length)
+    }
+}
+
+#[derive(Component, Default)]
+pub struct PathFollower {
+    pub lane: usize,
+    pub distance: f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Right 10, up 20, then a repeated point.
+    fn lane() -> LanePath {
+        LanePath::new("test".into(), vec![Vec2::ZERO, Vec2::new(10., 0.), Vec2::new(10., 20.), Vec2::new(10., 20.)])
+    }
+
+    #[test]
+    fn length_sums_the_segments() {
+        assert_eq!(lane().length(), 30.);
+    }
+
+    #[test]
+    fn sample_interpolates_along_segments() {
+        let lane = lane();
+
+        assert_eq!(lane.sample(5.), Vec2::new(5., 0.));
+        assert_eq!(lane.sample(10.), Vec2::new(10., 0.));
+        assert_eq!(lane.sample(20.), Vec2::new(10., 10.));
+    }
+
+    #[test]
+    fn sample_clamps_to_the_ends() {
+        let lane = lane();
+
+        assert_eq!(lane.sample(-5.), Vec2::ZERO);
+        assert_eq!(lane.sample(0.), Vec2::ZERO);
+        assert_eq!(lane.sample(30.), Vec2::new(10., 20.));
+        assert_eq!(lane.sample(1000.), Vec2::new(10., 20.));
+    }
+
+    #[test]
+    fn empty_and_single_point_lanes_do_not_panic() {
+        let empty = LanePath::new("empty".into(), Vec::new());
+        assert_eq!(empty.length(), 0.);
+        assert_eq!(empty.sample(5.), Vec2::ZERO);
+
+        let point = LanePath::new("point".into(), vec![Vec2::ONE]);
+        assert_eq!(point.sample(5.), Vec2::ONE);
+    }
+
+    #[test]
+    fn merged_lanes_continue_along_their_target() {
+        let lane = |name: &str, waypoints: Vec<Vec2>, merges_into: Option<LaneMerge>| Lane { name: name.into(), waypoints, merges_into };
+        let map = GameMap {
+            lanes: vec![
+                lane("main", vec![Vec2::ZERO, Vec2::X, Vec2::new(2., 0.)], None),
+                lane("side", vec![Vec2::new(1., 5.)], Some(LaneMerge { lane: "main".into(), waypoint: 1 })),
+                // Merging into itself stops after a few rounds instead of recursing forever
+                lane("loop", vec![Vec2::Y], Some(LaneMerge { lane: "loop".into(), waypoint: 0 }))
+            ],
+            build_grid: BuildGridDefinition { cell_size: 1., origin: Vec2::ZERO, columns: 0, rows: 0, path_width: 0., blocked: Vec::new() }
+        };
+
+        let paths = MapPaths::from_map(&map);
+
+        assert_eq!(paths.get(1).unwrap().points, vec![Vec2::new(1., 5.), Vec2::X, Vec2::new(2., 0.)]);
+        assert_eq!(paths.get(2).unwrap().points.len(), MapPaths::MAX_MERGE_DEPTH + 1);
+        assert_eq!(paths.lane_index("side"), Some(1));
+        assert_eq!(paths.lane_index("missing"), None);
+    }
+}
@@ -0,0 +1,111 @@
+use std::fmt;
+
+use bevy::prelude::{Handle, Resource};
+use serde::{Deserialize, Serialize};
+
+use super::{paths::GameMap, simulation::PlayerCommand, waves::WaveSet};
+
+/// Bumped whenever the replay format or the simulation changes in a way older replays don't play back the same.
+pub const REPLAY_VERSION: u32 = 1;
+pub const REPLAY_PATH: &str = "replays/latest.replay.ron";
+
+/// Speed the simulation runs at while seeking.
+pub const SEEK_SPEED: f32 = 50.;
+pub const PLAYBACK_SPEEDS: [f32; 4] = [0.5, 1., 2., 4.];
+
+/// Seeking back from less than this many ticks after a wave started goes to the wave before it.
+pub const SEEK_BACK_GRACE: u64 = 60;
+
+/// Read first, so outdated replays can be told apart from corrupt ones.
+#[derive(Deserialize)]
+pub struct ReplayHeader {
+    pub version: u32
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Replay {
+    pub version: u32,
+    pub seed: u64,
+    pub map: String,
+    pub waves: String,
+    pub commands: Vec<RecordedCommand>,
+    /// Tick each wave started on, in order.
+    pub wave_starts: Vec<u64>,
+    /// Tick the session ended on.
+    pub end: u64
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RecordedCommand {
+    pub tick: u64,
+    pub command: PlayerCommand
+}
+
+/// Commands of the running session, written to `REPLAY_PATH` when it ends.
+#[derive(Resource)]
+pub struct ReplayRecorder(pub Replay);
+
+/// Replay being played back, player input is ignored while it exists.
+#[derive(Resource)]
+pub struct ReplayPlayback {
+    pub replay: Replay,
+    /// Index of the next command to send.
+    pub next: usize,
+    pub paused: bool,
+    pub speed: f32,
+    /// Tick to fast forward to, playback continues as before once it is reached.
+    pub seek_to: Option<u64>
+}
+
+impl ReplayPlayback {
+    pub fn new(replay: Replay) -> Self {
+        Self {
+            replay,
+            next: 0,
+            paused: false,
+            speed: 1.,
+            seek_to: None
+        }
+    }
+
+    /// Start of the latest wave before `tick`, or the session start.
+    pub fn previous_wave_start(&self, tick: u64) -> u64 {
+        self.replay.wave_starts.iter()
+            .rev()
+            .find(|start| **start + SEEK_BACK_GRACE < tick)
+            .copied()
+            .unwrap_or(0)
+    }
+
+    pub fn next_wave_start(&self, tick: u64) -> Option<u64> {
+        self.replay.wave_starts.iter().find(|start| **start > tick).copied()
+    }
+}
+
+/// Map and waves chosen before a replay loaded its own, put back once playback stops.
+#[derive(Resource)]
+pub struct ReplacedAssets {
+    pub map: Handle<GameMap>,
+    pub waves: Handle<WaveSet>
+}
+
+#[derive(Debug)]
+pub enum ReplayError {
+    Missing,
+    Io(std::io::Error),
+    Corrupt(String),
+    Outdated { found: u32, expected: u32 }
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplayError::Missing => write!(f, "no replay found at {REPLAY_PATH}"),
+            ReplayError::Io(err) => write!(f, "could not access {REPLAY_PATH}: {err}"),
+            ReplayError::Corrupt(err) => write!(f, "replay file {REPLAY_PATH} is corrupt: {err}"),
+            ReplayError::Outdated { found, expected } => write!(f, "replay file {REPLAY_PATH} has version {found}, expected {expected}")
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
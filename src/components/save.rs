@@ -0,0 +1,125 @@
+use std::fmt;
+
+use bevy::{math::Vec2, prelude::Resource};
+use serde::{Deserialize, Serialize};
+
+use crate::TurretType;
+
+use super::{damage::DamageType, enemies::EnemyType, session::SessionStats, status_effects::StatusEffect, turrets::{Explosive, Homing, TargetingPriority}};
+
+/// Bumped whenever the save format changes in a way older saves can't be read with.
+pub const SAVE_VERSION: u32 = 2;
+pub const SAVE_PATH: &str = "saves/latest.save.ron";
+
+/// Read first, so outdated saves can be told apart from corrupt ones.
+#[derive(Deserialize)]
+pub struct SaveHeader {
+    pub version: u32
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SaveGame {
+    pub version: u32,
+    pub seed: u64,
+    pub tick: u64,
+    pub wave_index: usize,
+    pub phase: SavedWavePhase,
+    pub money: u32,
+    pub lives: u32,
+    pub turrets: Vec<SavedTurret>,
+    pub enemies: Vec<SavedEnemy>,
+    pub projectiles: Vec<SavedProjectile>,
+    #[serde(default)]
+    pub stats: SessionStats
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub enum SavedWavePhase {
+    Build { remaining: f32 },
+    Spawning { groups: Vec<SavedGroup> },
+    AwaitingClear,
+    Finished
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SavedGroup {
+    pub enemy: EnemyType,
+    pub lane: usize,
+    pub remaining: u32,
+    pub spacing: f32,
+    pub timer_duration: f32,
+    pub timer_elapsed: f32
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SavedTurret {
+    pub turret_type: TurretType,
+    pub position: Vec2,
+    pub rotation: f32,
+    pub upgrades: Vec<usize>,
+    pub priority: TargetingPriority,
+    pub invested: u32,
+    pub damage_dealt: f32,
+    pub kills: u32
+}
+
+/// Stealth comes from the enemy's definition and `Revealed` is worked out again every tick, so neither is saved.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SavedEnemy {
+    pub enemy_type: EnemyType,
+    pub lane: usize,
+    pub distance: f32,
+    pub health: f32,
+    #[serde(default)]
+    pub status_effects: Vec<SavedStatusEffect>
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SavedStatusEffect {
+    pub effect: StatusEffect,
+    pub stacks: u32,
+    pub elapsed: f32,
+    /// Index into `SaveGame::turrets` of the turret that applied it.
+    pub source: Option<usize>
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SavedProjectile {
+    pub position: Vec2,
+    pub rotation: f32,
+    pub damage: f32,
+    pub speed: f32,
+    pub damage_type: DamageType,
+    /// Index into `SaveGame::turrets` of the turret that fired it.
+    pub fired_by: Option<usize>,
+    pub homing: Option<Homing>,
+    pub explosive: Option<Explosive>,
+    pub on_hit_effect: Option<StatusEffect>
+}
+
+/// Save restored when the next session starts.
+#[derive(Resource)]
+pub struct PendingLoad(pub SaveGame);
+
+#[derive(Debug)]
+pub enum SaveError {
+    Missing,
+    Io(std::io::Error),
+    Serialize(String),
+    Corrupt(String),
+    Outdated { found: u32, expected: u32 }
+}
+
+impl fmt::Display for SaveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SaveError::Missing => write!(f, "no save found at {SAVE_PATH}"),
+            SaveError::Io(err) => write!(f, "could not access {SAVE_PATH}: {err}"),
+            SaveError::Serialize(err) => write!(f, "could not serialize the game: {err}"),
+            SaveError::Corrupt(err) => write!(f, "save file {SAVE_PATH} is corrupt: {err}"),
+            SaveError::Outdated { found, expected } => write!(f, "save file {SAVE_PATH} has version {found}, expected {expected}")
+        }
+    }
+}
+
+impl std::error::Error for SaveError {}
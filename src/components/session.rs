@@ -0,0 +1,35 @@
+use bevy::prelude::{Component, Event, Resource};
+use serde::{Deserialize, Serialize};
+
+pub const STARTING_LIVES: u32 = 20;
+
+#[derive(Resource)]
+pub struct Lives(pub u32);
+
+impl Default for Lives {
+    fn default() -> Self {
+        Self(STARTING_LIVES)
+    }
+}
+
+/// Totals shown on the results screen once the session ends.
+#[derive(Resource, Default, Clone, Debug, Serialize, Deserialize)]
+pub struct SessionStats {
+    pub waves_cleared: usize,
+    pub enemies_killed: u32,
+    pub enemies_leaked: u32,
+    pub money_earned: u64,
+    pub money_spent: u64,
+    pub turrets_built: u32
+}
+
+/// Where enemies leave the map, one per distinct lane end.
+#[derive(Component)]
+pub struct ExitPoint;
+
+/// Sent when an asset the session needs could not be loaded, loading is abandoned.
+#[derive(Event, Debug)]
+pub struct LoadingFailed {
+    pub path: String,
+    pub error: String
+}
@@ -0,0 +1,73 @@
+use bevy::{math::{IVec2, Quat, Vec3}, prelude::{Component, Event, Resource, Transform}};
+use serde::{Deserialize, Serialize};
+
+use crate::TurretType;
+
+pub const SIMULATION_HZ: f64 = 60.;
+pub const GAME_SPEEDS: [f32; 3] = [1., 2., 3.];
+
+/// Every player action that changes the simulation. Commands are applied on the fixed timestep,
+/// so the same seed and the same commands always play out the same way.
+#[derive(Event, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum PlayerCommand {
+    Build { turret_type: TurretType, cell: IVec2 },
+    Sell { cell: IVec2 },
+    Upgrade { cell: IVec2, branch: usize },
+    CycleTargeting { cell: IVec2 },
+    CallNextWave,
+    /// Doesn't change the outcome, recorded so replays can show how the session was played.
+    SetGameSpeed { speed: f32 }
+}
+
+/// Fixed steps simulated since the session started.
+#[derive(Resource, Default, Debug, Clone, Copy)]
+pub struct SimulationTick(pub u64);
+
+/// Order in which a simulated entity was spawned during the session. Query order depends on when the
+/// render plugin adds its components, so anything that must replay the same sorts by this instead.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SpawnId(pub u64);
+
+/// Hands out the session's spawn ids.
+#[derive(Resource, Default, Debug)]
+pub struct SpawnCounter(u64);
+
+impl SpawnCounter {
+    pub fn assign(&mut self) -> SpawnId {
+        self.0 += 1;
+        SpawnId(self.0)
+    }
+}
+
+/// Seed the session's random number generator was started with.
+#[derive(Resource, Default, Debug, Clone, Copy)]
+pub struct SessionSeed(pub u64);
+
+/// Seed the next session starts with instead of a random one, used for replays and challenge runs.
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct RequestedSeed(pub u64);
+
+/// Simulated translations and rotations of the last two fixed steps, rendered in between.
+#[derive(Component)]
+pub struct Interpolated {
+    pub previous: Vec3,
+    pub current: Vec3,
+    pub previous_rotation: Quat,
+    pub current_rotation: Quat,
+    /// Laser beams stretch to their target.
+    pub previous_scale: Vec3,
+    pub current_scale: Vec3
+}
+
+impl Interpolated {
+    pub fn new(transform: &Transform) -> Self {
+        Self {
+            previous: transform.translation,
+            current: transform.translation,
+            previous_rotation: transform.rotation,
+            current_rotation: transform.rotation,
+            previous_scale: transform.scale,
+            current_scale: transform.scale
+        }
+    }
+}
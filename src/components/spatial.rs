@@ -0,0 +1,167 @@
+use std::collections::BTreeMap;
+
+use bevy::{math::{IVec2, Vec2}, prelude::{Entity, Resource}};
+
+pub const ENEMY_GRID_CELL_SIZE: f32 = 64.;
+
+#[derive(Debug, Clone, Copy)]
+pub struct GridEnemy {
+    pub entity: Entity,
+    pub pos: Vec2,
+    pub radius: f32
+}
+
+/// Uniform grid of the enemies' positions, rebuilt every tick so hit and range checks only look at nearby enemies.
+#[derive(Resource)]
+pub struct EnemyGrid {
+    cell_size: f32,
+    /// Keyed by column then row, so cells are always visited in the same order.
+    cells: BTreeMap<(i32, i32), Vec<GridEnemy>>,
+    /// Largest hit radius in the grid, enemies are stored by their centre but can reach into neighbouring cells.
+    max_radius: f32
+}
+
+impl Default for EnemyGrid {
+    fn default() -> Self {
+        Self {
+            cell_size: ENEMY_GRID_CELL_SIZE,
+            cells: BTreeMap::new(),
+            max_radius: 0.
+        }
+    }
+}
+
+impl EnemyGrid {
+    /// Empties the grid but keeps the cells' allocations for the next tick.
+    pub fn clear(&mut self) {
+        for cell in self.cells.values_mut() {
+            cell.clear();
+        }
+
+        self.max_radius = 0.;
+    }
+
+    pub fn insert(&mut self, entity: Entity, pos: Vec2, radius: f32) {
+        let cell = self.cell_of(pos);
+        self.cells.entry((cell.x, cell.y)).or_default().push(GridEnemy { entity, pos, radius });
+        self.max_radius = self.max_radius.max(radius);
+    }
+
+    fn cell_of(&self, pos: Vec2) -> IVec2 {
+        (pos / self.cell_size).floor().as_ivec2()
+    }
+
+    /// Enemies stored in the cells a circle of `reach` around `center` touches. They come out by
+    /// column, then row, then in the order they were inserted, which `rebuild_enemy_grid_system`
+    /// keeps to spawn order. Only occupied cells are visited, so a huge reach stays cheap.
+    fn candidates(&self, center: Vec2, reach: f32) -> impl Iterator<Item = &GridEnemy> {
+        let min = self.cell_of(center - Vec2::splat(reach));
+        let max = self.cell_of(center + Vec2::splat(reach));
+
+        self.cells.range((min.x, min.y)..=(max.x, max.y))
+            .filter(move |((_, y), _)| (min.y..=max.y).contains(y))
+            .flat_map(|(_, enemies)| enemies)
+    }
+
+    /// Enemies whose hit circle overlaps the circle.
+    pub fn overlapping(&self, center: Vec2, radius: f32) -> impl Iterator<Item = &GridEnemy> {
+        self.candidates(center, radius + self.max_radius)
+            .filter(move |enemy| enemy.pos.distance(center) <= radius + enemy.radius)
+    }
+
+    /// Enemies whose centre is within `radius`, regardless of their size.
+    pub fn within(&self, center: Vec2, radius: f32) -> impl Iterator<Item = &GridEnemy> {
+        self.candidates(center, radius)
+            .filter(move |enemy| enemy.pos.distance(center) <= radius)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RADIUS: f32 = 12.;
+
+    /// Enemies on a lattice spanning several cells, including ones right on cell borders and below zero.
+    fn filled_grid() -> (EnemyGrid, Vec<GridEnemy>) {
+        let mut grid = EnemyGrid::default();
+        let mut enemies = Vec::new();
+
+        for x in -10..=10_i32 {
+            for y in -10..=10 {
+                let enemy = GridEnemy {
+                    entity: Entity::from_raw(enemies.len() as u32),
+                    pos: Vec2::new(x as f32, y as f32) * ENEMY_GRID_CELL_SIZE / 4.,
+                    radius: RADIUS * ((x + y).rem_euclid(3) + 1) as f32 / 3.
+                };
+
+                grid.insert(enemy.entity, enemy.pos, enemy.radius);
+                enemies.push(enemy);
+            }
+        }
+
+        (grid, enemies)
+    }
+
+    fn sorted(enemies: impl Iterator<Item = Entity>) -> Vec<Entity> {
+        let mut enemies: Vec<_> = enemies.collect();
+        enemies.sort();
+        enemies
+    }
+
+    fn queries() -> Vec<(Vec2, f32)> {
+        let centers = [
+            Vec2::ZERO,
+            Vec2::splat(ENEMY_GRID_CELL_SIZE),
+            Vec2::new(-ENEMY_GRID_CELL_SIZE, ENEMY_GRID_CELL_SIZE * 2.),
+            Vec2::new(ENEMY_GRID_CELL_SIZE - 0.5, -ENEMY_GRID_CELL_SIZE + 0.5),
+            Vec2::new(37., -101.)
+        ];
+        let radii = [0., 1., ENEMY_GRID_CELL_SIZE / 4., ENEMY_GRID_CELL_SIZE, ENEMY_GRID_CELL_SIZE * 1.5, 1e4, 1e9];
+
+        centers.into_iter().flat_map(|center| radii.map(|radius| (center, radius))).collect()
+    }
+
+    #[test]
+    fn within_matches_brute_force() {
+        let (grid, enemies) = filled_grid();
+
+        for (center, radius) in queries() {
+            let expected = sorted(enemies.iter().filter(|enemy| enemy.pos.distance(center) <= radius).map(|enemy| enemy.entity));
+            let found = sorted(grid.within(center, radius).map(|enemy| enemy.entity));
+            assert_eq!(found, expected, "within({center}, {radius})");
+        }
+    }
+
+    #[test]
+    fn overlapping_matches_brute_force() {
+        let (grid, enemies) = filled_grid();
+
+        for (center, radius) in queries() {
+            let expected = sorted(enemies.iter().filter(|enemy| enemy.pos.distance(center) <= radius + enemy.radius).map(|enemy| enemy.entity));
+            let found = sorted(grid.overlapping(center, radius).map(|enemy| enemy.entity));
+            assert_eq!(found, expected, "overlapping({center}, {radius})");
+        }
+    }
+
+    #[test]
+    fn cells_keep_insertion_order() {
+        let mut grid = EnemyGrid::default();
+        let order: Vec<_> = [5, 2, 9, 1].into_iter().map(Entity::from_raw).collect();
+
+        for entity in &order {
+            grid.insert(*entity, Vec2::splat(10.), RADIUS);
+        }
+
+        let found: Vec<_> = grid.within(Vec2::splat(10.), RADIUS).map(|enemy| enemy.entity).collect();
+        assert_eq!(found, order);
+    }
+
+    #[test]
+    fn cleared_grid_is_empty() {
+        let (mut grid, _) = filled_grid();
+        grid.clear();
+
+        assert_eq!(grid.overlapping(Vec2::ZERO, 1e9).count(), 0);
+    }
+}
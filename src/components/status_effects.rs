@@ -0,0 +1,266 @@
+use bevy::{color::Color, prelude::{Component, Entity, Event}, time::{Timer, TimerMode}};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum StatusEffectKind {
+    Slow { factor: f32 },
+    Burn { damage_per_second: f32 },
+    Corrosion { armor_reduction: f32 },
+    Stun
+}
+
+impl StatusEffectKind {
+    fn same_kind(&self, other: &StatusEffectKind) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+
+    fn strength(&self) -> f32 {
+        match self {
+            StatusEffectKind::Slow { factor } => 1. - factor,
+            StatusEffectKind::Burn { damage_per_second } => *damage_per_second,
+            StatusEffectKind::Corrosion { armor_reduction } => *armor_reduction,
+            StatusEffectKind::Stun => 1.
+        }
+    }
+
+    /// Effects with a higher priority decide the enemy's tint when several are active.
+    fn tint_priority(&self) -> u8 {
+        match self {
+            StatusEffectKind::Slow { .. } => 0,
+            StatusEffectKind::Corrosion { .. } => 1,
+            StatusEffectKind::Burn { .. } => 2,
+            StatusEffectKind::Stun => 3
+        }
+    }
+
+    pub fn tint(&self) -> Color {
+        match self {
+            StatusEffectKind::Slow { .. } => Color::srgb(0.4, 0.8, 1.0),
+            StatusEffectKind::Burn { .. } => Color::srgb(1.0, 0.45, 0.1),
+            StatusEffectKind::Corrosion { .. } => Color::srgb(0.5, 1.0, 0.2),
+            StatusEffectKind::Stun => Color::srgb(1.0, 1.0, 0.4)
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StackingRule {
+    /// A new application replaces the current one and restarts its duration.
+    #[default]
+    Refresh,
+    /// Every application adds a stack, up to `max_stacks`, and restarts the duration.
+    Stack { max_stacks: u32 },
+    /// Only the strongest application is kept, weaker ones just extend the duration.
+    KeepStrongest
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatusEffect {
+    pub kind: StatusEffectKind,
+    pub duration: f32,
+    #[serde(default)]
+    pub stacking: StackingRule
+}
+
+pub struct ActiveEffect {
+    pub effect: StatusEffect,
+    pub stacks: u32,
+    pub timer: Timer,
+    pub source: Option<Entity>
+}
+
+#[derive(Component, Default)]
+pub struct StatusEffects {
+    pub active: Vec<ActiveEffect>
+}
+
+impl StatusEffects {
+    pub fn apply(&mut self, effect: &StatusEffect, source: Option<Entity>) {
+        let Some(existing) = self.active.iter_mut().find(|active| active.effect.kind.same_kind(&effect.kind)) else {
+            self.active.push(ActiveEffect {
+                effect: effect.clone(),
+                stacks: 1,
+                timer: Timer::from_seconds(effect.duration, TimerMode::Once),
+                source
+            });
+            return;
+        };
+
+        match effect.stacking {
+            StackingRule::Refresh => {
+                existing.effect = effect.clone();
+                existing.timer = Timer::from_seconds(effect.duration, TimerMode::Once);
+                existing.source = source;
+            },
+            StackingRule::Stack { max_stacks } => {
+                existing.stacks = (existing.stacks + 1).min(max_stacks.max(1));
+                existing.timer = Timer::from_seconds(effect.duration, TimerMode::Once);
+                existing.source = source;
+            },
+            StackingRule::KeepStrongest => {
+                if effect.kind.strength() >= existing.effect.kind.strength() {
+                    existing.effect = effect.clone();
+                    existing.source = source;
+                }
+
+                if effect.duration > existing.timer.remaining_secs() {
+                    existing.timer = Timer::from_seconds(effect.duration, TimerMode::Once);
+                }
+            }
+        }
+    }
+
+    pub fn is_stunned(&self) -> bool {
+        self.active.iter().any(|active| matches!(active.effect.kind, StatusEffectKind::Stun))
+    }
+
+    pub fn speed_multiplier(&self) -> f32 {
+        if self.is_stunned() {
+            return 0.;
+        }
+
+        self.active.iter()
+            .filter_map(|active| match active.effect.kind {
+                StatusEffectKind::Slow { factor } => Some(factor.clamp(0., 1.).powi(active.stacks as i32)),
+                _ => None
+            })
+            .product()
+    }
+
+    pub fn armor_reduction(&self) -> f32 {
+        self.active.iter()
+            .filter_map(|active| match active.effect.kind {
+                StatusEffectKind::Corrosion { armor_reduction } => Some(armor_reduction * active.stacks as f32),
+                _ => None
+            })
+            .sum()
+    }
+
+    /// Color of the active effect with the highest tint priority, used to tint the enemy.
+    pub fn tint(&self) -> Option<Color> {
+        self.active.iter()
+            .max_by_key(|active| active.effect.kind.tint_priority())
+            .map(|active| active.effect.kind.tint())
+    }
+}
+
+#[derive(Component)]
+pub struct OnHitEffect(pub StatusEffect);
+
+#[derive(Component)]
+pub struct BaseColor(pub Color);
+
+#[derive(Event)]
+pub struct ApplyStatusEffect {
+    pub target: Entity,
+    pub effect: StatusEffect,
+    pub source: Option<Entity>
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn effect(kind: StatusEffectKind, duration: f32, stacking: StackingRule) -> StatusEffect {
+        StatusEffect { kind, duration, stacking }
+    }
+
+    fn slow(factor: f32, duration: f32, stacking: StackingRule) -> StatusEffect {
+        effect(StatusEffectKind::Slow { factor }, duration, stacking)
+    }
+
+    #[test]
+    fn different_kinds_are_tracked_separately() {
+        let mut effects = StatusEffects::default();
+        effects.apply(&slow(0.5, 2., StackingRule::Refresh), None);
+        effects.apply(&effect(StatusEffectKind::Burn { damage_per_second: 3. }, 2., StackingRule::Refresh), None);
+
+        assert_eq!(effects.active.len(), 2);
+    }
+
+    #[test]
+    fn refresh_replaces_the_effect_and_restarts_it() {
+        let source = Entity::from_raw(1);
+        let mut effects = StatusEffects::default();
+        effects.apply(&slow(0.5, 2., StackingRule::Refresh), None);
+        effects.active[0].timer.tick(std::time::Duration::from_secs(1));
+
+        effects.apply(&slow(0.8, 3., StackingRule::Refresh), Some(source));
+
+        let active = &effects.active[0];
+        assert_eq!(effects.active.len(), 1);
+        assert_eq!(active.effect.kind, StatusEffectKind::Slow { factor: 0.8 });
+        assert_eq!(active.stacks, 1);
+        assert_eq!(active.timer.remaining_secs(), 3.);
+        assert_eq!(active.source, Some(source));
+    }
+
+    #[test]
+    fn stacks_are_capped() {
+        let rule = StackingRule::Stack { max_stacks: 3 };
+        let mut effects = StatusEffects::default();
+
+        for _ in 0..5 {
+            effects.apply(&effect(StatusEffectKind::Corrosion { armor_reduction: 2. }, 1., rule), None);
+        }
+
+        assert_eq!(effects.active[0].stacks, 3);
+        assert_eq!(effects.armor_reduction(), 6.);
+    }
+
+    #[test]
+    fn stacking_with_no_max_still_applies_once() {
+        let rule = StackingRule::Stack { max_stacks: 0 };
+        let mut effects = StatusEffects::default();
+        effects.apply(&slow(0.5, 1., rule), None);
+        effects.apply(&slow(0.5, 1., rule), None);
+
+        assert_eq!(effects.active[0].stacks, 1);
+    }
+
+    #[test]
+    fn stacked_slows_multiply() {
+        let rule = StackingRule::Stack { max_stacks: 5 };
+        let mut effects = StatusEffects::default();
+        effects.apply(&slow(0.5, 1., rule), None);
+        effects.apply(&slow(0.5, 1., rule), None);
+
+        assert_eq!(effects.speed_multiplier(), 0.25);
+    }
+
+    #[test]
+    fn keep_strongest_ignores_weaker_applications() {
+        let strong = Entity::from_raw(1);
+        let weak = Entity::from_raw(2);
+        let mut effects = StatusEffects::default();
+        effects.apply(&slow(0.3, 1., StackingRule::KeepStrongest), Some(strong));
+        effects.apply(&slow(0.9, 4., StackingRule::KeepStrongest), Some(weak));
+
+        let active = &effects.active[0];
+        assert_eq!(active.effect.kind, StatusEffectKind::Slow { factor: 0.3 });
+        assert_eq!(active.source, Some(strong));
+        assert_eq!(active.timer.remaining_secs(), 4., "a weaker application still extends the duration");
+    }
+
+    #[test]
+    fn keep_strongest_does_not_shorten_the_duration() {
+        let mut effects = StatusEffects::default();
+        effects.apply(&slow(0.9, 4., StackingRule::KeepStrongest), None);
+        effects.apply(&slow(0.3, 1., StackingRule::KeepStrongest), None);
+
+        let active = &effects.active[0];
+        assert_eq!(active.effect.kind, StatusEffectKind::Slow { factor: 0.3 });
+        assert_eq!(active.timer.remaining_secs(), 4.);
+    }
+
+    #[test]
+    fn stun_stops_movement() {
+        let mut effects = StatusEffects::default();
+        effects.apply(&slow(0.5, 1., StackingRule::Refresh), None);
+        effects.apply(&effect(StatusEffectKind::Stun, 1., StackingRule::Refresh), None);
+
+        assert!(effects.is_stunned());
+        assert_eq!(effects.speed_multiplier(), 0.);
+        assert_eq!(effects.tint(), Some(StatusEffectKind::Stun.tint()));
+    }
+}
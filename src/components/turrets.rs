@@ -1,10 +1,91 @@
-use std::time::Duration;
+use std::collections::HashMap;
 
-use bevy::{color::Color, math::{Vec2, Vec3}, prelude::{Component, Entity}, time::{Timer, TimerMode}};
-use bevy_rand::prelude::EntropyComponent;
+use bevy::{asset::Asset, color::Color, math::{Vec2, Vec3}, prelude::{Component, Entity, Handle, Resource}, reflect::TypePath, time::{Timer, TimerMode}};
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumIter};
 
 use crate::TurretType;
 
+use super::{damage::DamageType, status_effects::StatusEffect};
+
+#[derive(Asset, TypePath, Deserialize, Debug)]
+#[serde(transparent)]
+pub struct TurretDefinitions(pub HashMap<TurretType, TurretDefinition>);
+
+impl TurretDefinitions {
+    pub fn get(&self, turret_type: &TurretType) -> Option<&TurretDefinition> {
+        self.0.get(turret_type)
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TurretDefinition {
+    pub sprite: String,
+    pub cost: u32,
+    pub damage_type: DamageType,
+    pub attack: TurretAttack,
+    #[serde(default)]
+    pub targeting_radius: Option<f32>,
+    #[serde(default)]
+    pub rotation_speed: Option<f32>,
+    #[serde(default)]
+    pub attack_delay: Option<f32>,
+    #[serde(default)]
+    pub dispersion: Option<f32>,
+    #[serde(default)]
+    pub spawn_offset: Option<Vec2>,
+    #[serde(default)]
+    pub homing: Option<Homing>,
+    #[serde(default)]
+    pub explosive: Option<Explosive>,
+    #[serde(default)]
+    pub on_hit_effect: Option<StatusEffect>
+}
+
+impl TurretDefinition {
+    /// Radius shown around a turret, its targeting range or the area it affects.
+    pub fn range(&self) -> Option<f32> {
+        match &self.attack {
+            TurretAttack::AoE { range, .. } => Some(*range),
+            TurretAttack::Aura { radius, .. } => Some(*radius),
+            _ => self.targeting_radius
+        }
+    }
+
+    pub fn base_stats(&self) -> BaseStats {
+        BaseStats {
+            attack_delay: self.attack_delay,
+            targeting_radius: self.targeting_radius,
+            rotation_speed: self.rotation_speed
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub enum TurretAttack {
+    Projectile { damage: f32, speed: f32 },
+    Laser { damage_per_second: f32 },
+    RailGun { charge_time: f32, max_range: f32, damage: f32, falloff: f32, beam_width: f32 },
+    ChainLightning { damage: f32, jumps: u32, jump_range: f32, falloff: f32 },
+    AoE {
+        range: f32,
+        damage: f32,
+        #[serde(default)]
+        always_attacking: bool
+    },
+    Aura { radius: f32, fire_rate_bonus: f32, range_bonus: f32, rotation_speed_bonus: f32 }
+}
+
+impl TurretAttack {
+    /// Whether the turret has to aim at a single target to attack.
+    pub fn is_targeted(&self) -> bool {
+        !matches!(self, TurretAttack::AoE { .. } | TurretAttack::Aura { .. })
+    }
+}
+
+#[derive(Resource)]
+pub struct CurrentTurretDefinitions(pub Handle<TurretDefinitions>);
+
 #[derive(Component)]
 pub struct Turret(pub TurretType);
 
@@ -12,7 +93,39 @@ pub struct Turret(pub TurretType);
 pub struct TargetingTurret {
     pub targeting_radius: Option<f32>,
     pub rotation: f32,
-    pub has_target: bool
+    pub has_target: bool,
+    pub target: Option<Entity>,
+    pub priority: TargetingPriority
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Display, EnumIter, Serialize, Deserialize)]
+pub enum TargetingPriority {
+    #[default]
+    First,
+    Last,
+    Strongest,
+    Weakest,
+    Closest,
+    Fastest
+}
+
+impl TargetingPriority {
+    pub fn next(&self) -> Self {
+        match self {
+            TargetingPriority::First => TargetingPriority::Last,
+            TargetingPriority::Last => TargetingPriority::Strongest,
+            TargetingPriority::Strongest => TargetingPriority::Weakest,
+            TargetingPriority::Weakest => TargetingPriority::Closest,
+            TargetingPriority::Closest => TargetingPriority::Fastest,
+            TargetingPriority::Fastest => TargetingPriority::First,
+        }
+    }
+}
+
+#[derive(Component, Default)]
+pub struct TurretStats {
+    pub damage_dealt: f32,
+    pub kills: u32
 }
 
 #[derive(Component)]
@@ -64,6 +177,61 @@ pub struct LaserTurret {
     pub laser_beam: Option<Entity>
 }
 
+#[derive(Component)]
+pub struct BuffAura {
+    pub radius: f32,
+    pub fire_rate_bonus: f32,
+    pub range_bonus: f32,
+    pub rotation_speed_bonus: f32,
+    /// Stealthed enemies are revealed this far beyond the aura's radius.
+    pub detection_bonus: f32
+}
+
+impl BuffAura {
+    pub fn detection_radius(&self) -> f32 {
+        self.radius + self.detection_bonus
+    }
+}
+
+/// Turret stats before any aura bonuses are applied.
+#[derive(Component, Clone)]
+pub struct BaseStats {
+    pub attack_delay: Option<f32>,
+    pub targeting_radius: Option<f32>,
+    pub rotation_speed: Option<f32>
+}
+
+#[derive(Component, Default)]
+pub struct Buffed {
+    pub sources: Vec<Entity>,
+    pub fire_rate_bonus: f32,
+    pub range_bonus: f32,
+    pub rotation_speed_bonus: f32
+}
+
+/// Hits the target and then jumps to the closest enemies not hit yet.
+#[derive(Component)]
+pub struct ChainLightningTurret {
+    pub jumps: u32,
+    pub jump_range: f32,
+    pub falloff: f32
+}
+
+#[derive(Component)]
+pub struct LightningArc {
+    pub points: Vec<Vec2>,
+    pub timer: Timer
+}
+
+#[derive(Component)]
+pub struct RailGunTurret {
+    pub charge: Timer,
+    pub max_range: f32,
+    pub damage: f32,
+    pub falloff: f32,
+    pub beam_width: f32
+}
+
 
 
 #[derive(Component)]
@@ -75,6 +243,9 @@ pub struct Projectile {
 #[derive(Component)]
 pub struct LaserBeam;
 
+#[derive(Component)]
+pub struct RailGunBeam;
+
 #[derive(Component)]
 pub struct LinearVelocity(pub f32);
 
@@ -99,14 +270,14 @@ pub enum DecayType {
     Despawn
 }
 
-#[derive(Component)]
+#[derive(Component, Clone, Debug, Serialize, Deserialize)]
 pub struct Homing{
     pub homing_distance: f32,
     pub homing_angle: f32,
     pub homing_speed: f32
 }
 
-#[derive(Component)]
+#[derive(Component, Clone, Debug, Serialize, Deserialize)]
 pub struct Explosive {
     pub radius: f32,
     pub damage: f32
@@ -116,7 +287,9 @@ pub struct Explosive {
 pub struct ExplosionToSpawn {
     pub radius: f32,
     pub damage: f32,
-    pub pos: Vec3
+    pub pos: Vec3,
+    pub source: Option<Entity>,
+    pub damage_type: DamageType
 }
 
 #[derive(Component)]
@@ -148,11 +321,9 @@ pub struct ColorAnimation {
 #[derive(Component)]
 pub struct AoEAttack {
     pub radius: f32,
-    pub pos: Vec3
-}
-
-#[derive(Component)]
-pub struct Target {
-    pub pos: Vec3
+    pub pos: Vec3,
+    pub damage: f32,
+    pub damage_type: DamageType,
+    pub effect: Option<StatusEffect>,
+    pub source: Option<Entity>
 }
-
@@ -0,0 +1,90 @@
+use std::collections::HashMap;
+
+use bevy::{asset::Asset, prelude::{Component, Entity, Event, Handle, Resource}, reflect::TypePath};
+use serde::Deserialize;
+
+use crate::TurretType;
+
+use super::turrets::{Explosive, Homing};
+
+#[derive(Asset, TypePath, Deserialize, Debug)]
+#[serde(transparent)]
+pub struct UpgradeTrees(pub HashMap<TurretType, UpgradeTree>);
+
+impl UpgradeTrees {
+    pub fn next_tier(&self, turret_type: &TurretType, upgrades: &TurretUpgrades, branch: usize) -> Option<&UpgradeTier> {
+        let branch_tiers = &self.0.get(turret_type)?.branches.get(branch)?.tiers;
+        branch_tiers.get(upgrades.tier(branch))
+    }
+
+    pub fn purchased_tiers<'a>(&'a self, turret_type: &TurretType, upgrades: &'a TurretUpgrades) -> impl Iterator<Item = &'a UpgradeTier> {
+        self.0.get(turret_type)
+            .into_iter()
+            .flat_map(|tree| tree.branches.iter().enumerate())
+            .flat_map(|(branch, upgrade_branch)| upgrade_branch.tiers.iter().take(upgrades.tier(branch)))
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct UpgradeTree {
+    pub branches: Vec<UpgradeBranch>
+}
+
+#[derive(Deserialize, Debug)]
+pub struct UpgradeBranch {
+    pub name: String,
+    pub tiers: Vec<UpgradeTier>
+}
+
+#[derive(Deserialize, Debug)]
+pub struct UpgradeTier {
+    pub name: String,
+    pub cost: u32,
+    pub modifiers: Vec<UpgradeModifier>
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub enum UpgradeModifier {
+    AttackDelayMultiplier(f32),
+    DispersionMultiplier(f32),
+    TargetingRadiusBonus(f32),
+    ProjectileSpeedMultiplier(f32),
+    DamageMultiplier(f32),
+    AuraRadiusBonus(f32),
+    DetectionRadiusBonus(f32),
+    AddHoming(Homing),
+    AddExplosive(Explosive)
+}
+
+#[derive(Resource)]
+pub struct CurrentUpgradeTrees(pub Handle<UpgradeTrees>);
+
+/// Number of purchased tiers in every branch of the turret's upgrade tree.
+#[derive(Component, Default, Clone, Debug)]
+pub struct TurretUpgrades {
+    pub tiers: Vec<usize>
+}
+
+impl TurretUpgrades {
+    pub fn tier(&self, branch: usize) -> usize {
+        self.tiers.get(branch).copied().unwrap_or(0)
+    }
+
+    pub fn advance(&mut self, branch: usize) {
+        if self.tiers.len() <= branch {
+            self.tiers.resize(branch + 1, 0);
+        }
+
+        self.tiers[branch] += 1;
+    }
+}
+
+/// Marks a turret whose stats were reset and need the purchased upgrades applied again.
+#[derive(Component)]
+pub struct ReplayUpgrades;
+
+#[derive(Event)]
+pub struct UpgradeTurret {
+    pub turret: Entity,
+    pub branch: usize
+}
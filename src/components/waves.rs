@@ -0,0 +1,102 @@
+use std::time::Duration;
+
+use bevy::{asset::Asset, prelude::{Event, Handle, Resource}, reflect::TypePath, time::{Timer, TimerMode}};
+use serde::Deserialize;
+
+use super::enemies::EnemyType;
+
+#[derive(Asset, TypePath, Deserialize, Debug)]
+pub struct WaveSet {
+    pub build_time: f32,
+    #[serde(default)]
+    pub early_call_bonus_per_second: f32,
+    pub waves: Vec<WaveDefinition>
+}
+
+impl WaveSet {
+    pub fn build_time(&self, wave_index: usize) -> f32 {
+        self.waves.get(wave_index)
+            .and_then(|wave| wave.build_time)
+            .unwrap_or(self.build_time)
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct WaveDefinition {
+    #[serde(default)]
+    pub build_time: Option<f32>,
+    pub groups: Vec<EnemyGroup>
+}
+
+#[derive(Deserialize, Debug)]
+pub struct EnemyGroup {
+    pub enemy: EnemyType,
+    pub count: u32,
+    pub spacing: f32,
+    #[serde(default)]
+    pub delay: f32,
+    pub lane: String
+}
+
+#[derive(Resource)]
+pub struct CurrentWaves(pub Handle<WaveSet>);
+
+#[derive(Resource, Default)]
+pub struct WaveSpawner {
+    pub wave_index: usize,
+    pub phase: WavePhase,
+    pub groups: Vec<GroupSpawner>
+}
+
+impl WaveSpawner {
+    pub fn build_time_remaining(&self) -> Option<f32> {
+        match &self.phase {
+            WavePhase::Build(timer) => Some(timer.remaining_secs()),
+            _ => None
+        }
+    }
+}
+
+#[derive(Default)]
+pub enum WavePhase {
+    #[default]
+    Loading,
+    Build(Timer),
+    Spawning,
+    AwaitingClear,
+    Finished
+}
+
+pub struct GroupSpawner {
+    pub enemy: EnemyType,
+    pub lane: usize,
+    pub remaining: u32,
+    pub spacing: Duration,
+    pub timer: Timer
+}
+
+impl GroupSpawner {
+    pub fn new(group: &EnemyGroup, lane: usize) -> Self {
+        Self {
+            enemy: group.enemy,
+            lane,
+            remaining: group.count,
+            spacing: Duration::from_secs_f32(group.spacing),
+            timer: Timer::from_seconds(group.delay, TimerMode::Once)
+        }
+    }
+}
+
+#[derive(Event)]
+pub struct CallNextWave;
+
+#[derive(Event)]
+pub struct WaveStarted {
+    pub index: usize,
+    pub early_call_bonus: u32
+}
+
+#[derive(Event)]
+pub struct WaveCleared {
+    pub index: usize
+}
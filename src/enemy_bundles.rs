@@ -0,0 +1,40 @@
+use bevy::prelude::*;
+
+use crate::components::{damage::Resistances, enemies::*, paths::PathFollower, status_effects::*};
+
+
+#[derive(Bundle)]
+pub struct EnemyBundle {
+    pub marker: Enemy,
+    pub health: Health,
+    pub armor: Armor,
+    pub speed: MovementSpeed,
+    pub bounty: Bounty,
+    pub hit_radius: HitRadius,
+    pub resistances: Resistances,
+    pub status_effects: StatusEffects,
+    pub base_color: BaseColor,
+    pub path_follower: PathFollower,
+    pub spatial: TransformBundle
+}
+
+impl EnemyBundle {
+    pub fn new(enemy_type: EnemyType, definition: &EnemyDefinition, lane: usize, pos: Vec3) -> Self {
+        Self {
+            marker: Enemy(enemy_type),
+            health: Health::new(definition.health),
+            armor: Armor(definition.armor),
+            speed: MovementSpeed(definition.speed),
+            bounty: Bounty(definition.bounty),
+            hit_radius: HitRadius(definition.radius),
+            resistances: definition.resistances.clone(),
+            status_effects: StatusEffects::default(),
+            base_color: BaseColor(definition.color()),
+            path_follower: PathFollower {
+                lane,
+                distance: 0.
+            },
+            spatial: TransformBundle::from_transform(Transform::from_translation(pos))
+        }
+    }
+}
@@ -0,0 +1,327 @@
+use std::{fs, num::NonZeroU8, path::PathBuf, time::{Duration, Instant}};
+
+use bevy::{prelude::*, state::app::StatesPlugin, time::TimeUpdateStrategy};
+use bevy_prng::ChaCha8Rng;
+use bevy_rand::plugin::EntropyPlugin;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    components::{build::BuildGrid, economy::*, enemies::{CurrentEnemyDefinitions, EnemyLeaked}, paths::CurrentMap, session::*, simulation::*, turrets::*, upgrades::CurrentUpgradeTrees, waves::*},
+    states::{GameState, GameplaySet},
+    systems::{enemy_movement_system, GameplayPlugin},
+    TurretType, DEFAULT_ENEMIES, DEFAULT_MAP, DEFAULT_TURRETS, DEFAULT_UPGRADES, DEFAULT_WAVES
+};
+
+/// One hour of game time.
+const DEFAULT_MAX_TICKS: u64 = 60 * 60 * SIMULATION_HZ as u64;
+/// Real time the assets may take to load before the run is given up.
+const LOADING_TIMEOUT: Duration = Duration::from_secs(30);
+
+pub const USAGE: &str = "usage: headless [--map <asset>] [--waves <asset>] [--layout <file>] [--seed <number>] [--max-ticks <number>]";
+
+/// Exit code for invalid arguments.
+const USAGE_ERROR: u8 = 2;
+
+pub struct HeadlessOptions {
+    pub map: String,
+    pub waves: String,
+    pub layout: Option<PathBuf>,
+    pub seed: Option<u64>,
+    pub max_ticks: u64
+}
+
+impl HeadlessOptions {
+    pub fn parse(args: &[String]) -> Result<Self, String> {
+        let mut options = Self {
+            map: DEFAULT_MAP.into(),
+            waves: DEFAULT_WAVES.into(),
+            layout: None,
+            seed: None,
+            max_ticks: DEFAULT_MAX_TICKS
+        };
+
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            if arg == "--headless" {
+                continue;
+            }
+
+            let value = args.next().ok_or_else(|| format!("missing value for {arg}"))?;
+            let number = || value.parse::<u64>().map_err(|err| format!("invalid value '{value}' for {arg}: {err}"));
+
+            match arg.as_str() {
+                "--map" => options.map.clone_from(value),
+                "--waves" => options.waves.clone_from(value),
+                "--layout" => options.layout = Some(value.into()),
+                "--seed" => options.seed = Some(number()?),
+                "--max-ticks" => options.max_ticks = number()?,
+                _ => return Err(format!("unknown argument {arg}"))
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+/// Turrets placed at the start of a headless run.
+#[derive(Deserialize, Default)]
+pub struct TurretLayout {
+    #[serde(default)]
+    pub starting_money: Option<u32>,
+    pub turrets: Vec<LayoutTurret>
+}
+
+#[derive(Deserialize)]
+pub struct LayoutTurret {
+    pub turret_type: TurretType,
+    pub cell: IVec2,
+    /// Upgrade branches, bought in order.
+    #[serde(default)]
+    pub upgrades: Vec<usize>
+}
+
+#[derive(Resource)]
+struct HeadlessRun {
+    options: HeadlessOptions,
+    layout: TurretLayout
+}
+
+#[derive(Resource, Default)]
+struct RunStats {
+    leaks_per_wave: Vec<u32>,
+    rejected_builds: u32
+}
+
+#[derive(Serialize)]
+struct Summary {
+    seed: u64,
+    ticks: u64,
+    outcome: &'static str,
+    waves_cleared: usize,
+    lives_left: u32,
+    lives_lost: u32,
+    leaks_per_wave: Vec<u32>,
+    money: MoneySummary,
+    rejected_builds: u32,
+    turrets: Vec<TurretSummary>
+}
+
+#[derive(Serialize)]
+struct Failure {
+    outcome: &'static str,
+    error: String
+}
+
+#[derive(Serialize)]
+struct MoneySummary {
+    remaining: u32,
+    earned: u64,
+    spent: u64
+}
+
+#[derive(Serialize)]
+struct TurretSummary {
+    turret_type: TurretType,
+    cell: Option<IVec2>,
+    damage_dealt: f32,
+    kills: u32
+}
+
+/// Parses the command line and runs the session, `--headless` is accepted and ignored so the game can forward its arguments.
+pub fn run_from_args(args: &[String]) -> AppExit {
+    match HeadlessOptions::parse(args).and_then(run) {
+        Ok(exit) => exit,
+        Err(err) => {
+            eprintln!("{err}\n{USAGE}");
+            AppExit::Error(NonZeroU8::new(USAGE_ERROR).unwrap())
+        }
+    }
+}
+
+/// Simulates a whole session without a window and prints a JSON summary to stdout.
+/// Exits with an error if the session could not be loaded.
+pub fn run(options: HeadlessOptions) -> Result<AppExit, String> {
+    let layout = match &options.layout {
+        Some(path) => {
+            let content = fs::read_to_string(path).map_err(|err| format!("could not read {}: {err}", path.display()))?;
+            ron::from_str(&content).map_err(|err| format!("invalid layout {}: {err}", path.display()))?
+        },
+        None => TurretLayout::default()
+    };
+
+    let mut app = App::new();
+    app
+        .add_plugins(MinimalPlugins)
+        .add_plugins((AssetPlugin::default(), StatesPlugin, EntropyPlugin::<ChaCha8Rng>::default()))
+        .init_state::<GameState>()
+        // Every update advances exactly one fixed step, as fast as the machine allows
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(1. / SIMULATION_HZ)))
+        .init_resource::<RunStats>()
+        .add_plugins(GameplayPlugin)
+        .add_systems(Update, (loading_failed_system.run_if(on_event::<LoadingFailed>()), loading_timeout_system.run_if(in_state(GameState::Loading))))
+        .add_systems(Update, apply_layout_system.run_if(in_state(GameState::InGame)))
+        .add_systems(Update, timeout_system.run_if(in_state(GameState::InGame)))
+        .add_systems(FixedUpdate, (count_leaks_system.after(enemy_movement_system), count_rejections_system).in_set(GameplaySet))
+        .add_systems(OnEnter(GameState::GameOver), report_system)
+        .add_systems(OnEnter(GameState::Victory), report_system);
+
+    // Inserted before running, `Loading` is entered before commands of startup systems are applied
+    let asset_server = app.world().resource::<AssetServer>().clone();
+    app
+        .insert_resource(CurrentMap(asset_server.load(&options.map)))
+        .insert_resource(CurrentWaves(asset_server.load(&options.waves)))
+        .insert_resource(CurrentEnemyDefinitions(asset_server.load(DEFAULT_ENEMIES)))
+        .insert_resource(CurrentUpgradeTrees(asset_server.load(DEFAULT_UPGRADES)))
+        .insert_resource(CurrentTurretDefinitions(asset_server.load(DEFAULT_TURRETS)));
+
+    if let Some(seed) = options.seed {
+        app.insert_resource(RequestedSeed(seed));
+    }
+
+    if let Some(money) = layout.starting_money {
+        app.world_mut().resource_mut::<EconomySettings>().starting_money = money;
+    }
+
+    app.world_mut().resource_mut::<NextState<GameState>>().set(GameState::Loading);
+
+    Ok(app.insert_resource(HeadlessRun { options, layout }).run())
+}
+
+/// Builds on the first frame and upgrades on the second, once the turrets occupy their cells.
+fn apply_layout_system(
+    mut stage: Local<u8>,
+    run: Res<HeadlessRun>,
+    mut commands: EventWriter<PlayerCommand>
+) {
+    match *stage {
+        0 => {
+            commands.send_batch(run.layout.turrets.iter().map(|turret| PlayerCommand::Build {
+                turret_type: turret.turret_type.clone(),
+                cell: turret.cell
+            }));
+        },
+        1 => {
+            commands.send_batch(run.layout.turrets.iter().flat_map(|turret| {
+                turret.upgrades.iter().map(|branch| PlayerCommand::Upgrade { cell: turret.cell, branch: *branch })
+            }));
+        },
+        _ => return
+    }
+
+    *stage += 1;
+}
+
+fn count_leaks_system(
+    mut events: EventReader<EnemyLeaked>,
+    spawner: Res<WaveSpawner>,
+    mut stats: ResMut<RunStats>
+) {
+    for _ in events.read() {
+        let wave = spawner.wave_index;
+        if stats.leaks_per_wave.len() <= wave {
+            stats.leaks_per_wave.resize(wave + 1, 0);
+        }
+
+        stats.leaks_per_wave[wave] += 1;
+    }
+}
+
+fn count_rejections_system(
+    mut events: EventReader<BuildRejected>,
+    mut stats: ResMut<RunStats>
+) {
+    stats.rejected_builds += events.read().count() as u32;
+}
+
+fn loading_failed_system(
+    mut events: EventReader<LoadingFailed>,
+    exit: EventWriter<AppExit>
+) {
+    if let Some(event) = events.read().next() {
+        report_failure(format!("could not load {}: {}", event.path, event.error), exit);
+    }
+}
+
+fn loading_timeout_system(
+    mut started: Local<Option<Instant>>,
+    exit: EventWriter<AppExit>
+) {
+    if started.get_or_insert_with(Instant::now).elapsed() >= LOADING_TIMEOUT {
+        report_failure(format!("assets did not load within {}s", LOADING_TIMEOUT.as_secs()), exit);
+    }
+}
+
+fn report_failure(error: String, mut exit: EventWriter<AppExit>) {
+    let failure = Failure { outcome: "Error", error };
+
+    match serde_json::to_string_pretty(&failure) {
+        Ok(json) => println!("{json}"),
+        Err(err) => eprintln!("could not write summary: {err}")
+    }
+
+    exit.send(AppExit::error());
+}
+
+fn timeout_system(
+    tick: Res<SimulationTick>,
+    run: Res<HeadlessRun>,
+    mut next_state: ResMut<NextState<GameState>>
+) {
+    if tick.0 >= run.options.max_ticks {
+        next_state.set(GameState::GameOver);
+    }
+}
+
+fn report_system(
+    state: Res<State<GameState>>,
+    seed: Res<SessionSeed>,
+    tick: Res<SimulationTick>,
+    run: Res<HeadlessRun>,
+    stats: Res<RunStats>,
+    session: Res<SessionStats>,
+    lives: Res<Lives>,
+    wallet: Res<Wallet>,
+    grid: Res<BuildGrid>,
+    turrets: Query<(Entity, &Turret, &TurretStats)>,
+    mut exit: EventWriter<AppExit>
+) {
+    let outcome = match state.get() {
+        GameState::Victory => "Victory",
+        _ if lives.0 > 0 && tick.0 >= run.options.max_ticks => "Timeout",
+        _ => "GameOver"
+    };
+
+    let mut waves = stats.leaks_per_wave.clone();
+    waves.resize(waves.len().max(session.waves_cleared), 0);
+
+    let summary = Summary {
+        seed: seed.0,
+        ticks: tick.0,
+        outcome,
+        waves_cleared: session.waves_cleared,
+        lives_left: lives.0,
+        lives_lost: STARTING_LIVES.saturating_sub(lives.0),
+        leaks_per_wave: waves,
+        money: MoneySummary {
+            remaining: wallet.money,
+            earned: session.money_earned,
+            spent: session.money_spent
+        },
+        rejected_builds: stats.rejected_builds,
+        turrets: turrets.iter()
+            .map(|(entity, turret, turret_stats)| TurretSummary {
+                turret_type: turret.0.clone(),
+                cell: grid.cell_of(entity),
+                damage_dealt: turret_stats.damage_dealt,
+                kills: turret_stats.kills
+            })
+            .collect()
+    };
+
+    match serde_json::to_string_pretty(&summary) {
+        Ok(json) => println!("{json}"),
+        Err(err) => eprintln!("could not write summary: {err}")
+    }
+
+    exit.send(AppExit::Success);
+}
@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumCount, EnumIter};
+
+pub mod assets;
+pub mod components;
+pub mod systems;
+pub mod states;
+pub mod turret_bundles;
+pub mod enemy_bundles;
+pub mod headless;
+
+pub const ARROW_SIZE: (f32, f32) = (50., 50.);
+
+pub const DEFAULT_MAP: &str = "maps/default.map.ron";
+pub const DEFAULT_WAVES: &str = "waves/default.waves.ron";
+pub const DEFAULT_ENEMIES: &str = "enemies/default.enemies.ron";
+pub const DEFAULT_UPGRADES: &str = "upgrades/default.upgrades.ron";
+pub const DEFAULT_TURRETS: &str = "turrets/default.turrets.ron";
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, Display, EnumIter, EnumCount, Serialize, Deserialize)]
+pub enum TurretType {
+    #[default]
+    PulseBlaster,   // ✔
+    IonCannon,      // ✔
+    SwarmTurret,    // ✔
+    PlasmaRay,      // ✔
+    RailGun,        // ✔
+    CryoGenerator,  // ✔
+    Tesla,          // ✔
+    SeekerLauncher, // ✔
+    AcidSprayer,    // ✔
+    FireThrower,    // ✔
+    Sentinel        // ✔
+}
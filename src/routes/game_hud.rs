@@ -0,0 +1,559 @@
+use crate::{components::{build::BuildGrid, economy::Wallet, session::Lives, simulation::PlayerCommand, turrets::*, upgrades::*, waves::*}, view::{ArenaClicked, SelectedTurret, SelectedWeapon}, *};
+use ui::button::Button;
+
+const CARD_SLOTS: usize = 5;
+const UPGRADE_BRANCHES: usize = 2;
+
+#[derive(Component, Debug, Default, Clone, PartialEq)]
+pub struct GameHudRoute;
+
+#[derive(Resource, Default)]
+pub struct CurrentPage(pub WeaponPage);
+
+#[derive(Display, EnumIter, EnumCount, Clone, Copy, Default, PartialEq)]
+pub enum WeaponPage {
+    #[default]
+    StandardWeapons,
+    SpecialWeapons,
+    Building
+}
+
+impl WeaponPage {
+    fn from_u8(val: u8) -> Option<Self> {
+        match val {
+            0 => Some(WeaponPage::StandardWeapons),
+            1 => Some(WeaponPage::SpecialWeapons),
+            2 => Some(WeaponPage::Building),
+            _ => None,
+        }
+    }
+
+    fn of(turret_type: &TurretType) -> Self {
+        match turret_type {
+            TurretType::PulseBlaster
+            | TurretType::IonCannon
+            | TurretType::SwarmTurret
+            | TurretType::PlasmaRay
+            | TurretType::RailGun => WeaponPage::StandardWeapons,
+            TurretType::Tesla
+            | TurretType::SeekerLauncher
+            | TurretType::AcidSprayer
+            | TurretType::FireThrower => WeaponPage::SpecialWeapons,
+            TurretType::CryoGenerator
+            | TurretType::Sentinel => WeaponPage::Building,
+        }
+    }
+
+    fn turrets(&self) -> Vec<TurretType> {
+        TurretType::iter().filter(|turret_type| WeaponPage::of(turret_type) == *self).collect()
+    }
+}
+
+#[derive(Display, Clone, Copy)]
+pub enum PageNavigation {
+    Next,
+    Previous
+}
+
+#[derive(Component)]
+pub struct PageNavigationButton(pub PageNavigation);
+
+#[derive(Component)]
+pub struct TurretCard {
+    pub slot: usize,
+    pub turret: Option<TurretType>
+}
+
+#[derive(Component)]
+pub struct NextWaveButton;
+
+#[derive(Component)]
+pub struct GameArena;
+
+#[derive(Component, Clone, Copy)]
+enum HudCounter {
+    Lives,
+    Money,
+    Wave
+}
+
+/// Every node of the inspection panel, lunex nodes don't inherit visibility from their layout parent.
+#[derive(Component)]
+pub struct InspectorPanel;
+
+/// Catches clicks on the panel so they don't reach the arena below it.
+#[derive(Component)]
+pub struct InspectorZone;
+
+#[derive(Component, Clone, Copy, EnumIter)]
+enum InspectorField {
+    Type,
+    DamageDealt,
+    Kills,
+    FireRate,
+    Range
+}
+
+#[derive(Component, Clone, Copy)]
+enum InspectorAction {
+    Targeting,
+    Upgrade(usize),
+    Sell
+}
+
+/// Fills a turret bar slot with the turret at that position of the page, or an empty hidden card.
+fn card_content(
+    slot: usize,
+    page: WeaponPage,
+    definitions: Option<&TurretDefinitions>,
+    assets: &AssetServer
+) -> (Option<TurretType>, Button) {
+    let card = page.turrets().into_iter().nth(slot)
+        .and_then(|turret_type| {
+            let definition = definitions?.get(&turret_type)?;
+            let button = Button {
+                text: Some(format!("{turret_type} {}", definition.cost)),
+                image: Some(assets.load(&definition.sprite)),
+                hover_enlarge: true
+            };
+
+            Some((Some(turret_type), button))
+        });
+
+    card.unwrap_or_else(|| (None, Button {
+        text: Some(String::new()),
+        image: Some(Handle::default()),
+        hover_enlarge: true
+    }))
+}
+
+fn build_route(
+    mut commands: Commands,
+    assets: Res<AssetServer>,
+    page: Res<CurrentPage>,
+    current_definitions: Res<CurrentTurretDefinitions>,
+    definitions: Res<Assets<TurretDefinitions>>,
+    query: Query<Entity, Added<GameHudRoute>>,
+    mut materials: ResMut<Assets<ColorMaterial>>
+) {
+    let definitions = definitions.get(&current_definitions.0);
+
+    for route_entity in &query {
+        commands.entity(route_entity).insert(
+            SpatialBundle::default()
+        ).with_children(|route| {
+
+            route.spawn((
+                UiTreeBundle::<MainUi>::from(UiTree::new2d("Game")),
+                MovableByCamera
+            )).with_children(|ui| {
+
+                let root = UiLink::<MainUi>::path("Root");
+                ui.spawn((
+                    root.clone(),
+                    GameArena,
+                    UiLayout::window_full().pack::<Base>(),
+                    UiZoneBundle::default(),
+                    UiClickEmitter::SELF
+                ));
+
+                let top_row = root.add("Top Row");
+                ui.spawn((
+                    top_row.clone(),
+                    UiLayout::boundary()
+                        .pos1(Rl(0.))
+                        .pos2((Rl(100.), Vh(6.)))
+                        .pack::<Base>(),
+                    UiMaterial2dBundle {
+                        material: materials.add(Color::BLACK.with_alpha(0.5)),
+                        ..default()
+                    }
+                ));
+
+                for (i, counter) in [HudCounter::Lives, HudCounter::Money, HudCounter::Wave].into_iter().enumerate() {
+                    ui.spawn((
+                        top_row.add(format!("Counter {i}")),
+                        UiLayout::window()
+                            .pos((Rw(2. + 16. * i as f32), Rh(50.)))
+                            .anchor(Anchor::CenterLeft)
+                            .pack::<Base>(),
+                        UiTextSize::new().size(Rh(60.0)),
+                        UiText2dBundle {
+                            text: Text::from_section("",
+                                TextStyle {
+                                    font: assets.load(AssetPath::FONT_SEMIBOLD),
+                                    font_size: 60.0,
+                                    color: Color::BEVYPUNK_YELLOW
+                                }),
+                            ..default()
+                        },
+                        Pickable::IGNORE,
+                        counter
+                    ));
+                }
+
+                ui.spawn((
+                    top_row.add("Next Wave Button"),
+                    UiLayout::window()
+                        .pos((Rw(98.), Rh(50.)))
+                        .anchor(Anchor::CenterRight)
+                        .size((Rh(400.), Rh(70.)))
+                        .pack::<Base>(),
+                    MainButton {
+                        text: "NEXT WAVE".into()
+                    },
+                    NextWaveButton
+                ));
+
+                let inspector = root.add("Inspector");
+                ui.spawn((
+                    inspector.clone(),
+                    UiLayout::boundary()
+                        .pos1((Rw(80.), Vh(8.)))
+                        .pos2((Rw(99.), Vh(88.)))
+                        .pack::<Base>(),
+                    UiMaterial2dBundle {
+                        material: materials.add(Color::BLACK.with_alpha(0.5)),
+                        visibility: Visibility::Hidden,
+                        ..default()
+                    },
+                    InspectorPanel
+                ));
+
+                // Sibling of the panel rather than its child, so it stays below the panel's buttons
+                ui.spawn((
+                    root.add("Inspector Zone"),
+                    UiLayout::boundary()
+                        .pos1((Rw(80.), Vh(8.)))
+                        .pos2((Rw(99.), Vh(88.)))
+                        .pack::<Base>(),
+                    UiZoneBundle::default(),
+                    Visibility::Hidden,
+                    InspectorPanel,
+                    InspectorZone
+                ));
+
+                let line = 6.;
+                for (i, field) in InspectorField::iter().enumerate() {
+                    ui.spawn((
+                        inspector.add(format!("Field {i}")),
+                        UiLayout::window()
+                            .pos(Rl((6., 4. + line * (i as f32 + 0.5))))
+                            .anchor(Anchor::CenterLeft)
+                            .pack::<Base>(),
+                        UiTextSize::new().size(Rh(line * 0.7)),
+                        UiText2dBundle {
+                            text: Text::from_section("",
+                                TextStyle {
+                                    font: assets.load(AssetPath::FONT_MEDIUM),
+                                    font_size: 60.0,
+                                    color: Color::BEVYPUNK_YELLOW
+                                }),
+                            visibility: Visibility::Hidden,
+                            ..default()
+                        },
+                        Pickable::IGNORE,
+                        InspectorPanel,
+                        field
+                    ));
+                }
+
+                let actions = (0..UPGRADE_BRANCHES).map(InspectorAction::Upgrade)
+                    .chain([InspectorAction::Targeting, InspectorAction::Sell]);
+
+                for (i, action) in actions.enumerate() {
+                    ui.spawn((
+                        inspector.add(format!("Action {i}")),
+                        UiLayout::window()
+                            .pos(Rl((6., 45. + 11. * i as f32)))
+                            .size(Rl((88., 9.)))
+                            .pack::<Base>(),
+                        Button {
+                            text: Some(String::new()),
+                            image: None,
+                            hover_enlarge: false
+                        },
+                        Visibility::Hidden,
+                        InspectorPanel,
+                        action
+                    ));
+                }
+
+                let bottom_row = root.add("Bottom Row");
+                ui.spawn((
+                    bottom_row.clone(),
+                    UiLayout::boundary()
+                        .pos1((0., Vh(90.)))
+                        .pos2(Rl(100.))
+                        .pack::<Base>(),
+                    UiMaterial2dBundle {
+                        material: materials.add(Color::BLACK.with_alpha(0.5)),
+                        ..default()
+                    }
+                ));
+
+                ui.spawn((
+                    bottom_row.add("Previous Page Button"),
+                    UiLayout::window()
+                        .pos((38., Rh(50.)))
+                        .anchor(Anchor::Center)
+                        .size((38., 53.))
+                        .pack::<Base>(),
+                    Button {
+                        text: None,
+                        image: Some(assets.load(AssetPath::CHEVRON_LEFT)),
+                        hover_enlarge: true
+                    },
+                    PageNavigationButton(PageNavigation::Previous)
+                ));
+
+                ui.spawn((
+                    bottom_row.add("Next Page Button"),
+                    UiLayout::window()
+                        .pos((Rw(100.) - Ab(38.), Rh(50.)))
+                        .anchor(Anchor::Center)
+                        .size((38., 53.))
+                        .pack::<Base>(),
+                    Button {
+                        text: None,
+                        image: Some(assets.load(AssetPath::CHEVRON_RIGHT)),
+                        hover_enlarge: true
+                    },
+                    PageNavigationButton(PageNavigation::Next)
+                ));
+
+                let turret_selector = bottom_row.add("Turret Selector");
+                ui.spawn((
+                    turret_selector.clone(),
+                    UiLayout::boundary()
+                        .pos1((Ab(76.), Rl(0.)))
+                        .pos2((Rw(100.) - Ab(76.), Rl(100.)))
+                        .pack::<Base>()
+                ));
+
+                for slot in 0..CARD_SLOTS {
+                    let (turret, button) = card_content(slot, page.0, definitions, &assets);
+                    let x = (slot + 1) as f32 * 100. / (CARD_SLOTS + 1) as f32;
+
+                    ui.spawn((
+                        turret_selector.add(format!("{slot}")),
+                        UiLayout::window()
+                            .pos(Rl((x, 40.)))
+                            .anchor(Anchor::Center)
+                            .size((Rh(50.), Rh(50.)))
+                            .pack::<Base>(),
+                        if turret.is_some() { Visibility::Inherited } else { Visibility::Hidden },
+                        button,
+                        TurretCard { slot, turret }
+                    ));
+                }
+            });
+        });
+    }
+}
+
+fn refresh_turret_cards_system(
+    assets: Res<AssetServer>,
+    page: Res<CurrentPage>,
+    current_definitions: Res<CurrentTurretDefinitions>,
+    definitions: Res<Assets<TurretDefinitions>>,
+    mut cards: Query<(&mut TurretCard, &mut Button, &mut Visibility)>
+) {
+    let definitions = definitions.get(&current_definitions.0);
+
+    for (mut card, mut button, mut visibility) in &mut cards {
+        let (turret, content) = card_content(card.slot, page.0, definitions, &assets);
+
+        visibility.set_if_neq(if turret.is_some() { Visibility::Inherited } else { Visibility::Hidden });
+        button.set_if_neq(content);
+        card.turret = turret;
+    }
+}
+
+fn update_counters_system(
+    lives: Res<Lives>,
+    wallet: Res<Wallet>,
+    spawner: Res<WaveSpawner>,
+    current_waves: Res<CurrentWaves>,
+    wave_sets: Res<Assets<WaveSet>>,
+    mut counters: Query<(&HudCounter, &mut Text)>
+) {
+    let total_waves = wave_sets.get(&current_waves.0).map_or(0, |wave_set| wave_set.waves.len());
+
+    for (counter, mut text) in &mut counters {
+        text.sections[0].value = match counter {
+            HudCounter::Lives => format!("LIVES {}", lives.0),
+            HudCounter::Money => format!("MONEY {}", wallet.money),
+            HudCounter::Wave => match spawner.build_time_remaining() {
+                Some(remaining) => format!("WAVE {}/{total_waves} IN {remaining:.0}s", spawner.wave_index + 1),
+                None => format!("WAVE {}/{total_waves}", (spawner.wave_index + 1).min(total_waves))
+            }
+        };
+    }
+}
+
+fn update_inspector_system(
+    mut commands: Commands,
+    selected: Res<SelectedTurret>,
+    current_trees: Res<CurrentUpgradeTrees>,
+    upgrade_trees: Res<Assets<UpgradeTrees>>,
+    turrets: Query<(&Turret, &TurretStats, Option<&AttackDelay>, Option<&TargetingTurret>, Option<&AoETurret>, Option<&BuffAura>, Option<&TurretUpgrades>)>,
+    mut panels: Query<&mut Visibility, With<InspectorPanel>>,
+    zones: Query<(Entity, Option<&Pickable>), With<InspectorZone>>,
+    mut fields: Query<(&InspectorField, &mut Text)>,
+    mut actions: Query<(&InspectorAction, &mut Button)>
+) {
+    let turret = selected.0.and_then(|entity| turrets.get(entity).ok());
+
+    for mut visibility in &mut panels {
+        visibility.set_if_neq(if turret.is_some() { Visibility::Inherited } else { Visibility::Hidden });
+    }
+
+    // Picking ignores visibility, the hidden panel must not block the arena
+    for (zone, pickable) in &zones {
+        if pickable.is_none_or(|pickable| pickable.should_block_lower) != turret.is_some() {
+            commands.entity(zone).insert(if turret.is_some() { Pickable::default() } else { Pickable::IGNORE });
+        }
+    }
+
+    let Some((turret, stats, attack_delay, targeting, aoe, aura, upgrades)) = turret else {
+        return;
+    };
+
+    for (field, mut text) in &mut fields {
+        text.sections[0].value = match field {
+            InspectorField::Type => turret.0.to_string(),
+            InspectorField::DamageDealt => format!("DAMAGE {:.0}", stats.damage_dealt),
+            InspectorField::Kills => format!("KILLS {}", stats.kills),
+            InspectorField::FireRate => match attack_delay {
+                Some(delay) if delay.0.duration().as_secs_f32() > 0. => format!("FIRE RATE {:.2}/s", 1. / delay.0.duration().as_secs_f32()),
+                _ => "FIRE RATE -".into()
+            },
+            InspectorField::Range => match turret_range(targeting, aoe, aura) {
+                Some(range) => format!("RANGE {range:.0}"),
+                None => "RANGE -".into()
+            }
+        };
+    }
+
+    let trees = upgrade_trees.get(&current_trees.0);
+    let default_upgrades = TurretUpgrades::default();
+
+    for (action, mut button) in &mut actions {
+        let label = match action {
+            InspectorAction::Targeting => match targeting {
+                Some(targeting) => format!("TARGET {}", targeting.priority),
+                None => "TARGET -".into()
+            },
+            InspectorAction::Upgrade(branch) => {
+                let tier = trees.and_then(|trees| trees.next_tier(&turret.0, upgrades.unwrap_or(&default_upgrades), *branch));
+                match tier {
+                    Some(tier) => format!("{} {}", tier.name, tier.cost),
+                    None => "MAXED".into()
+                }
+            },
+            InspectorAction::Sell => "SELL".into()
+        };
+
+        if button.text.as_deref() != Some(label.as_str()) {
+            button.text = Some(label);
+        }
+    }
+}
+
+fn inspector_button_clicked_system(
+    mut events: EventReader<UiClickEvent>,
+    actions: Query<&InspectorAction, With<Button>>,
+    selected: Res<SelectedTurret>,
+    grid: Res<BuildGrid>,
+    mut commands: EventWriter<PlayerCommand>
+) {
+    for event in events.read() {
+        let Ok(action) = actions.get(event.target) else {
+            continue;
+        };
+
+        let Some(cell) = selected.0.and_then(|turret| grid.cell_of(turret)) else {
+            continue;
+        };
+
+        commands.send(match action {
+            InspectorAction::Targeting => PlayerCommand::CycleTargeting { cell },
+            InspectorAction::Upgrade(branch) => PlayerCommand::Upgrade { cell, branch: *branch },
+            InspectorAction::Sell => PlayerCommand::Sell { cell }
+        });
+    }
+}
+
+fn hud_button_clicked_system(
+    mut events: EventReader<UiClickEvent>,
+    page_buttons: Query<&PageNavigationButton, With<Button>>,
+    cards: Query<&TurretCard, With<Button>>,
+    next_wave_buttons: Query<(), With<NextWaveButton>>,
+    game_arena: Query<(), With<GameArena>>,
+    mut page: ResMut<CurrentPage>,
+    mut selected: ResMut<SelectedWeapon>,
+    mut commands: EventWriter<PlayerCommand>,
+    mut arena_clicks: EventWriter<ArenaClicked>
+) {
+    for event in events.read() {
+        if let Ok(button) = page_buttons.get(event.target) {
+            let page_index = page.0 as u8;
+            let pages_count = WeaponPage::COUNT as u8;
+
+            let next_index = match button.0 {
+                PageNavigation::Next => (page_index + 1) % pages_count,
+                PageNavigation::Previous => (page_index + pages_count - 1) % pages_count,
+            };
+
+            if let Some(next_page) = WeaponPage::from_u8(next_index) {
+                page.0 = next_page;
+                info!("Current page: {}!", page.0);
+            }
+        } else if let Ok(card) = cards.get(event.target) {
+            if let Some(turret_type) = &card.turret {
+                info!("Selected {}", turret_type);
+                selected.0 = Some(turret_type.clone());
+            }
+        } else if next_wave_buttons.contains(event.target) {
+            commands.send(PlayerCommand::CallNextWave);
+        } else if game_arena.contains(event.target) {
+            arena_clicks.send(ArenaClicked);
+        }
+    }
+}
+
+fn spawn_game_hud(mut commands: Commands) {
+    commands.spawn(GameHudRoute);
+}
+
+fn despawn_game_hud(
+    mut commands: Commands,
+    routes: Query<Entity, With<GameHudRoute>>
+) {
+    for route in &routes {
+        commands.entity(route).despawn_recursive();
+    }
+}
+
+pub struct GameHudRoutePlugin;
+impl Plugin for GameHudRoutePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<CurrentPage>()
+            .add_systems(OnEnter(GameState::InGame), spawn_game_hud.run_if(accepts_player_input))
+            .add_systems(OnExit(GameState::InGame), despawn_game_hud)
+            .add_systems(PreUpdate, build_route.before(UiSystems::Compute))
+            .add_systems(Update, refresh_turret_cards_system
+                .run_if(resource_changed::<CurrentPage>.or_else(on_event::<AssetEvent<TurretDefinitions>>()))
+                .run_if(in_state(GameState::InGame)))
+            .add_systems(Update, (
+                update_counters_system,
+                update_inspector_system
+            ).run_if(in_state(GameState::InGame)))
+            .add_systems(Update, (hud_button_clicked_system, inspector_button_clicked_system)
+                .run_if(in_state(GameState::InGame))
+                .distributive_run_if(on_event::<UiClickEvent>())
+                .distributive_run_if(input_just_pressed(MouseButton::Left)));
+    }
+}
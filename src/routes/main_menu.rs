@@ -1,6 +1,4 @@
-use ui::button::Button;
-
-use crate::*;
+use crate::{components::{replay::ReplayPlayback, save::PendingLoad}, *};
 
 #[derive(Component, Debug, Default, Clone, PartialEq)]
 pub struct MainMenuRoute;
@@ -8,7 +6,6 @@ pub struct MainMenuRoute;
 
 fn build_route(
     mut commands: Commands,
-    assets: Res<AssetServer>,
     query: Query<Entity, Added<MainMenuRoute>>,
     mut materials: ResMut<Assets<ColorMaterial>>
 ) {
@@ -64,7 +61,7 @@ fn build_route(
                 let mut offset = 0.0;
 
                 for button_type in MainMenuButton::iter() {
-                    let mut button = ui.spawn((
+                    ui.spawn((
                         list.add(button_type.str()),
                         button_type.clone(),
                         UiLayout::window().y(Rl(offset)).size(Rl((100.0, size))).pack::<Base>(),
@@ -73,10 +70,6 @@ fn build_route(
                         }
                     ));
 
-                    if button_type == MainMenuButton::Continue {
-                        button.insert(OnUiClickDespawn::new(route_entity));
-                    }
-
                     offset += gap + size;
                 }
             });
@@ -88,6 +81,7 @@ fn build_route(
 enum MainMenuButton {
     Continue,
     NewGame,
+    Replay,
     Settings,
     QuitGame
 }
@@ -96,6 +90,7 @@ impl MainMenuButton {
         match self {
             MainMenuButton::Continue => "CONTINUE".into(),
             MainMenuButton::NewGame => "NEW GAME".into(),
+            MainMenuButton::Replay => "WATCH REPLAY".into(),
             MainMenuButton::Settings => "SETTINGS".into(),
             MainMenuButton::QuitGame => "QUIT GAME".into(),
         }
@@ -103,8 +98,12 @@ impl MainMenuButton {
 }
 
 fn main_menu_button_clicked_system(
+    mut commands: Commands,
     mut events: EventReader<UiClickEvent>,
-    query: Query<&MainMenuButton, With<Button>>,
+    query: Query<&MainMenuButton, With<MainButton>>,
+    routes: Query<Entity, With<MainMenuRoute>>,
+    state: Res<State<GameState>>,
+    mut next_state: ResMut<NextState<GameState>>,
     mut exit: EventWriter<AppExit>
 ) {
     for event in events.read() {
@@ -112,19 +111,72 @@ fn main_menu_button_clicked_system(
             info!("Pressed: {}", button.str());
 
             match button {
+                MainMenuButton::Continue => {
+                    if *state.get() == GameState::Paused {
+                        next_state.set(GameState::InGame);
+                        continue;
+                    }
+
+                    match load_save_game() {
+                        Ok(save) => {
+                            commands.remove_resource::<ReplayPlayback>();
+                            commands.insert_resource(PendingLoad(save));
+                            next_state.set(GameState::Loading);
+                        },
+                        Err(err) => error!("Cannot continue: {err}")
+                    }
+                },
+                MainMenuButton::NewGame => {
+                    commands.remove_resource::<PendingLoad>();
+                    commands.remove_resource::<ReplayPlayback>();
+                    next_state.set(GameState::Loading);
+                },
+                MainMenuButton::Replay => {
+                    match load_replay() {
+                        Ok(replay) => {
+                            commands.remove_resource::<PendingLoad>();
+                            commands.insert_resource(ReplayPlayback::new(replay));
+                            next_state.set(GameState::Loading);
+                        },
+                        Err(err) => error!("Cannot play replay: {err}")
+                    }
+                },
+                MainMenuButton::Settings => {
+                    for route in &routes {
+                        commands.entity(route).despawn_recursive();
+                    }
+
+                    commands.spawn(SettingsRoute);
+                },
                 MainMenuButton::QuitGame => {
                     exit.send(AppExit::Success);
-                },
-                _ => {}
+                }
             }
         }
     }
 }
 
+fn spawn_main_menu(mut commands: Commands) {
+    commands.spawn(MainMenuRoute);
+}
+
+fn despawn_main_menu(
+    mut commands: Commands,
+    routes: Query<Entity, With<MainMenuRoute>>
+) {
+    for route in &routes {
+        commands.entity(route).despawn_recursive();
+    }
+}
+
 pub struct MainMenuRoutePlugin;
 impl Plugin for MainMenuRoutePlugin {
     fn build(&self, app: &mut App) {
         app
+            .add_systems(OnEnter(GameState::MainMenu), spawn_main_menu)
+            .add_systems(OnEnter(GameState::Paused), spawn_main_menu)
+            .add_systems(OnExit(GameState::MainMenu), despawn_main_menu)
+            .add_systems(OnExit(GameState::Paused), despawn_main_menu)
             .add_systems(PreUpdate, build_route.before(UiSystems::Compute))
             .add_systems(Update, main_menu_button_clicked_system
                 .distributive_run_if(on_event::<UiClickEvent>())
@@ -1,11 +1,23 @@
 pub mod main_menu;
 pub use main_menu::*;
 
+pub mod settings;
+pub use settings::*;
+
+pub mod game_hud;
+pub use game_hud::*;
+
+pub mod results;
+pub use results::*;
+
+pub mod replay;
+pub use replay::*;
+
 use bevy::prelude::*;
 
 pub struct RoutePlugin;
 impl Plugin for RoutePlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins(MainMenuRoutePlugin);
+        app.add_plugins((MainMenuRoutePlugin, SettingsRoutePlugin, GameHudRoutePlugin, ResultsRoutePlugin, ReplayRoutePlugin));
     }
 }
@@ -0,0 +1,225 @@
+use crate::{components::{economy::Wallet, replay::*, session::Lives, simulation::{SimulationTick, SIMULATION_HZ}, waves::*}, *};
+
+#[derive(Component, Debug, Default, Clone, PartialEq)]
+pub struct ReplayRoute;
+
+#[derive(Component)]
+pub struct ReplayStatus;
+
+
+fn build_route(
+    mut commands: Commands,
+    assets: Res<AssetServer>,
+    query: Query<Entity, Added<ReplayRoute>>,
+    mut materials: ResMut<Assets<ColorMaterial>>
+) {
+    for route_entity in &query {
+        commands.entity(route_entity).insert(
+            SpatialBundle::default()
+        ).with_children(|route| {
+
+            route.spawn((
+                UiTreeBundle::<MainUi>::from(UiTree::new2d("Replay")),
+                MovableByCamera
+            )).with_children(|ui| {
+
+                let root = UiLink::<MainUi>::path("Root");
+                ui.spawn((
+                    root.clone(),
+                    UiLayout::window_full().pack::<Base>()
+                ));
+
+                let top_row = root.add("Top Row");
+                ui.spawn((
+                    top_row.clone(),
+                    UiLayout::boundary()
+                        .pos1(Rl(0.))
+                        .pos2((Rl(100.), Vh(6.)))
+                        .pack::<Base>(),
+                    UiMaterial2dBundle {
+                        material: materials.add(Color::BLACK.with_alpha(0.5)),
+                        ..default()
+                    }
+                ));
+
+                ui.spawn((
+                    top_row.add("Status"),
+                    UiLayout::window()
+                        .pos((Rw(2.), Rh(50.)))
+                        .anchor(Anchor::CenterLeft)
+                        .pack::<Base>(),
+                    UiTextSize::new().size(Rh(60.0)),
+                    UiText2dBundle {
+                        text: Text::from_section("",
+                            TextStyle {
+                                font: assets.load(AssetPath::FONT_SEMIBOLD),
+                                font_size: 60.0,
+                                color: Color::BEVYPUNK_YELLOW
+                            }),
+                        ..default()
+                    },
+                    Pickable::IGNORE,
+                    ReplayStatus
+                ));
+
+                let bottom_row = root.add("Bottom Row");
+                ui.spawn((
+                    bottom_row.clone(),
+                    UiLayout::boundary()
+                        .pos1((0., Vh(92.)))
+                        .pos2(Rl(100.))
+                        .pack::<Base>(),
+                    UiMaterial2dBundle {
+                        material: materials.add(Color::BLACK.with_alpha(0.5)),
+                        ..default()
+                    }
+                ));
+
+                let count = ReplayButton::iter().count();
+                for (i, button_type) in ReplayButton::iter().enumerate() {
+                    let x = (i + 1) as f32 * 100. / (count + 1) as f32;
+
+                    ui.spawn((
+                        bottom_row.add(format!("Button {i}")),
+                        UiLayout::window()
+                            .pos((Rw(x), Rh(50.)))
+                            .anchor(Anchor::Center)
+                            .size((Rw(14.), Rh(70.)))
+                            .pack::<Base>(),
+                        MainButton {
+                            text: button_type.str(false, 1.)
+                        },
+                        button_type
+                    ));
+                }
+            });
+        });
+    }
+}
+
+#[derive(Component, Clone, Copy, PartialEq, EnumIter)]
+enum ReplayButton {
+    PreviousWave,
+    PlayPause,
+    NextWave,
+    Speed,
+    Exit
+}
+impl ReplayButton {
+    fn str(&self, paused: bool, speed: f32) -> String {
+        match self {
+            ReplayButton::PreviousWave => "<< WAVE".into(),
+            ReplayButton::PlayPause if paused => "PLAY".into(),
+            ReplayButton::PlayPause => "PAUSE".into(),
+            ReplayButton::NextWave => "WAVE >>".into(),
+            ReplayButton::Speed => format!("SPEED {speed}x"),
+            ReplayButton::Exit => "EXIT".into()
+        }
+    }
+}
+
+fn format_time(tick: u64) -> String {
+    let seconds = (tick as f64 / SIMULATION_HZ) as u64;
+    format!("{:02}:{:02}", seconds / 60, seconds % 60)
+}
+
+fn update_replay_status_system(
+    playback: Res<ReplayPlayback>,
+    tick: Res<SimulationTick>,
+    lives: Res<Lives>,
+    wallet: Res<Wallet>,
+    spawner: Res<WaveSpawner>,
+    current_waves: Res<CurrentWaves>,
+    wave_sets: Res<Assets<WaveSet>>,
+    mut status: Query<&mut Text, With<ReplayStatus>>,
+    mut buttons: Query<(&ReplayButton, &mut MainButton)>
+) {
+    let total_waves = wave_sets.get(&current_waves.0).map_or(0, |wave_set| wave_set.waves.len());
+    let seeking = if playback.seek_to.is_some() { "   SEEKING..." } else { "" };
+
+    for mut text in &mut status {
+        text.sections[0].value = format!(
+            "REPLAY   {} / {}   WAVE {}/{total_waves}   LIVES {}   MONEY {}{seeking}",
+            format_time(tick.0),
+            format_time(playback.replay.end),
+            (spawner.wave_index + 1).min(total_waves),
+            lives.0,
+            wallet.money
+        );
+    }
+
+    for (button_type, mut button) in &mut buttons {
+        let text = button_type.str(playback.paused, playback.speed);
+        if button.text != text {
+            button.text = text;
+        }
+    }
+}
+
+fn replay_button_clicked_system(
+    mut events: EventReader<UiClickEvent>,
+    query: Query<&ReplayButton, With<MainButton>>,
+    mut playback: ResMut<ReplayPlayback>,
+    tick: Res<SimulationTick>,
+    mut next_state: ResMut<NextState<GameState>>
+) {
+    for event in events.read() {
+        let Ok(button) = query.get(event.target) else {
+            continue;
+        };
+
+        match button {
+            ReplayButton::PreviousWave => {
+                // The past can't be simulated backwards, play the replay again from the start up to the wave
+                playback.seek_to = Some(playback.previous_wave_start(tick.0));
+                next_state.set(GameState::Loading);
+            },
+            ReplayButton::PlayPause => {
+                playback.paused = !playback.paused;
+            },
+            ReplayButton::NextWave => {
+                if let Some(target) = playback.next_wave_start(tick.0) {
+                    playback.seek_to = Some(target);
+                }
+            },
+            ReplayButton::Speed => {
+                let current = PLAYBACK_SPEEDS.iter().position(|speed| *speed == playback.speed).unwrap_or(0);
+                playback.speed = PLAYBACK_SPEEDS[(current + 1) % PLAYBACK_SPEEDS.len()];
+            },
+            ReplayButton::Exit => {
+                next_state.set(GameState::MainMenu);
+            }
+        }
+    }
+}
+
+fn spawn_replay(mut commands: Commands) {
+    commands.spawn(ReplayRoute);
+}
+
+fn despawn_replay(
+    mut commands: Commands,
+    routes: Query<Entity, With<ReplayRoute>>
+) {
+    for route in &routes {
+        commands.entity(route).despawn_recursive();
+    }
+}
+
+pub struct ReplayRoutePlugin;
+impl Plugin for ReplayRoutePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(OnEnter(GameState::InGame), spawn_replay.run_if(resource_exists::<ReplayPlayback>))
+            .add_systems(OnExit(GameState::InGame), despawn_replay)
+            .add_systems(PreUpdate, build_route.before(UiSystems::Compute))
+            .add_systems(Update, update_replay_status_system
+                .run_if(resource_exists::<ReplayPlayback>)
+                .run_if(in_state(GameState::InGame)))
+            .add_systems(Update, replay_button_clicked_system
+                .run_if(resource_exists::<ReplayPlayback>)
+                .run_if(in_state(GameState::InGame))
+                .distributive_run_if(on_event::<UiClickEvent>())
+                .distributive_run_if(input_just_pressed(MouseButton::Left)));
+    }
+}
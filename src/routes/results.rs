@@ -0,0 +1,195 @@
+use crate::{components::{replay::ReplayPlayback, save::PendingLoad, session::{Lives, SessionStats}}, *};
+
+#[derive(Component, Debug, Default, Clone, PartialEq)]
+pub struct ResultsRoute;
+
+
+fn build_route(
+    mut commands: Commands,
+    assets: Res<AssetServer>,
+    state: Res<State<GameState>>,
+    stats: Res<SessionStats>,
+    lives: Res<Lives>,
+    query: Query<Entity, Added<ResultsRoute>>,
+    mut materials: ResMut<Assets<ColorMaterial>>
+) {
+    let (title, title_color) = match state.get() {
+        GameState::Victory => ("VICTORY", Color::srgb(0.4, 1.0, 0.5)),
+        _ => ("GAME OVER", Color::srgb(1.0, 0.3, 0.3))
+    };
+
+    let lines = [
+        format!("WAVES CLEARED  {}", stats.waves_cleared),
+        format!("LIVES LEFT  {}", lives.0),
+        format!("ENEMIES KILLED  {}", stats.enemies_killed),
+        format!("ENEMIES LEAKED  {}", stats.enemies_leaked),
+        format!("TURRETS BUILT  {}", stats.turrets_built),
+        format!("MONEY EARNED  {}", stats.money_earned),
+        format!("MONEY SPENT  {}", stats.money_spent)
+    ];
+
+    for route_entity in &query {
+        commands.entity(route_entity).insert(
+            SpatialBundle::default()
+        ).with_children(|route| {
+
+            route.spawn((
+                UiTreeBundle::<MainUi>::from(UiTree::new2d("Results")),
+                MovableByCamera
+            )).with_children(|ui| {
+
+                let root = UiLink::<MainUi>::path("Root");
+                ui.spawn((
+                    root.clone(),
+                    UiLayout::window_full().pack::<Base>()
+                ));
+
+                ui.spawn((
+                    root.add("Background"),
+                    UiLayout::solid().size((1920.0, 1080.0)).scaling(Scaling::Fill).pack::<Base>(),
+                    UiMaterial2dBundle {
+                        material: materials.add(Color::srgba(0.1, 0.1, 0.1, 0.7)),
+                        ..default()
+                    }
+                ));
+
+                let board = root.add("Solid");
+                ui.spawn((
+                    board.clone(),
+                    UiLayout::solid().size((881.0, 1600.0)).pack::<Base>(),
+                ));
+
+                let board = board.add("Board");
+                ui.spawn((
+                    board.clone(),
+                    UiLayout::window().x(Rl(50.0)).anchor(Anchor::TopCenter).size(Rl(105.0)).pack::<Base>(),
+                    UiMaterial2dBundle {
+                        material: materials.add(Color::BEVYPUNK_RED_DIM),
+                        ..default()
+                    }
+                ));
+
+                ui.spawn((
+                    board.add("Title"),
+                    UiLayout::window().pos(Rl((50.0, 14.0))).anchor(Anchor::Center).pack::<Base>(),
+                    UiTextSize::new().size(Rh(7.0)),
+                    UiText2dBundle {
+                        text: Text::from_section(title,
+                            TextStyle {
+                                font: assets.load(AssetPath::FONT_BOLD),
+                                font_size: 60.0,
+                                color: title_color
+                            }),
+                        ..default()
+                    },
+                    Pickable::IGNORE
+                ));
+
+                for (i, line) in lines.iter().enumerate() {
+                    ui.spawn((
+                        board.add(format!("Stat {i}")),
+                        UiLayout::window().pos(Rl((22.0, 26.0 + 5.0 * i as f32))).anchor(Anchor::CenterLeft).pack::<Base>(),
+                        UiTextSize::new().size(Rh(3.5)),
+                        UiText2dBundle {
+                            text: Text::from_section(line,
+                                TextStyle {
+                                    font: assets.load(AssetPath::FONT_MEDIUM),
+                                    font_size: 60.0,
+                                    color: Color::BEVYPUNK_YELLOW
+                                }),
+                            ..default()
+                        },
+                        Pickable::IGNORE
+                    ));
+                }
+
+                let list = board.add("List");
+                ui.spawn((
+                    list.clone(),
+                    UiLayout::window().pos(Rl((22.0, 66.0))).size(Rl((55.0, 17.0))).pack::<Base>()
+                ));
+
+                let gap = 6.0;
+                let size = 47.0;
+                let mut offset = 0.0;
+
+                for button_type in ResultsButton::iter() {
+                    ui.spawn((
+                        list.add(button_type.str()),
+                        button_type.clone(),
+                        UiLayout::window().y(Rl(offset)).size(Rl((100.0, size))).pack::<Base>(),
+                        MainButton {
+                            text: button_type.str()
+                        }
+                    ));
+
+                    offset += gap + size;
+                }
+            });
+        });
+    }
+}
+
+#[derive(Component, Clone, PartialEq, EnumIter)]
+enum ResultsButton {
+    Retry,
+    MainMenu
+}
+impl ResultsButton {
+    fn str(&self) -> String {
+        match self {
+            ResultsButton::Retry => "RETRY".into(),
+            ResultsButton::MainMenu => "MAIN MENU".into(),
+        }
+    }
+}
+
+fn results_button_clicked_system(
+    mut commands: Commands,
+    mut events: EventReader<UiClickEvent>,
+    query: Query<&ResultsButton, With<MainButton>>,
+    mut next_state: ResMut<NextState<GameState>>
+) {
+    for event in events.read() {
+        if let Ok(button) = query.get(event.target) {
+            match button {
+                ResultsButton::Retry => {
+                    commands.remove_resource::<PendingLoad>();
+                    commands.remove_resource::<ReplayPlayback>();
+                    next_state.set(GameState::Loading);
+                },
+                ResultsButton::MainMenu => {
+                    next_state.set(GameState::MainMenu);
+                }
+            }
+        }
+    }
+}
+
+fn spawn_results(mut commands: Commands) {
+    commands.spawn(ResultsRoute);
+}
+
+fn despawn_results(
+    mut commands: Commands,
+    routes: Query<Entity, With<ResultsRoute>>
+) {
+    for route in &routes {
+        commands.entity(route).despawn_recursive();
+    }
+}
+
+pub struct ResultsRoutePlugin;
+impl Plugin for ResultsRoutePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(OnEnter(GameState::GameOver), spawn_results)
+            .add_systems(OnEnter(GameState::Victory), spawn_results)
+            .add_systems(OnExit(GameState::GameOver), despawn_results)
+            .add_systems(OnExit(GameState::Victory), despawn_results)
+            .add_systems(PreUpdate, build_route.before(UiSystems::Compute))
+            .add_systems(Update, results_button_clicked_system
+                .distributive_run_if(on_event::<UiClickEvent>())
+                .distributive_run_if(input_just_pressed(MouseButton::Left)));
+    }
+}
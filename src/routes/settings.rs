@@ -0,0 +1,206 @@
+use crate::{settings::*, *};
+
+#[derive(Component, Debug, Default, Clone, PartialEq)]
+pub struct SettingsRoute;
+
+
+fn build_route(
+    mut commands: Commands,
+    settings: Res<Settings>,
+    query: Query<Entity, Added<SettingsRoute>>,
+    mut materials: ResMut<Assets<ColorMaterial>>
+) {
+    for route_entity in &query {
+        commands.entity(route_entity).insert(
+            SpatialBundle::default()
+        ).with_children(|route| {
+
+            route.spawn((
+                UiTreeBundle::<MainUi>::from(UiTree::new2d("Settings")),
+                MovableByCamera
+            )).with_children(|ui| {
+
+                let root = UiLink::<MainUi>::path("Root");
+                ui.spawn((
+                    root.clone(),
+                    UiLayout::window_full().pack::<Base>()
+                ));
+
+                ui.spawn((
+                    root.add("Background"),
+                    UiLayout::solid().size((1920.0, 1080.0)).scaling(Scaling::Fill).pack::<Base>(),
+                    UiMaterial2dBundle {
+                        material: materials.add(Color::srgba(0.5, 0.2, 0.2, 0.5)),
+                        ..default()
+                    }
+                ));
+
+                let board = root.add("Solid");
+                ui.spawn((
+                    board.clone(),
+                    UiLayout::solid().size((881.0, 1600.0)).align_x(-0.74).pack::<Base>(),
+                ));
+
+                let board = board.add("Board");
+                ui.spawn((
+                    board.clone(),
+                    UiLayout::window().x(Rl(50.0)).anchor(Anchor::TopCenter).size(Rl(105.0)).pack::<Base>(),
+                    UiMaterial2dBundle {
+                        material: materials.add(Color::BEVYPUNK_RED_DIM),
+                        ..default()
+                    }
+                ));
+
+                let list = board.add("List");
+                ui.spawn((
+                    list.clone(),
+                    UiLayout::window().pos(Rl((15.0, 18.0))).size(Rl((70.0, 64.0))).pack::<Base>()
+                ));
+
+                let gap = 2.0;
+                let size = 9.0;
+                let mut offset = 0.0;
+
+                ui.spawn((
+                    list.add("MasterVolume"),
+                    SettingsSlider::MasterVolume,
+                    UiLayout::window().y(Rl(offset)).size(Rl((100.0, size))).pack::<Base>(),
+                    Slider {
+                        text: "MASTER VOLUME".into(),
+                        value: settings.master_volume
+                    }
+                ));
+
+                offset += gap + size;
+
+                for button_type in SettingsButton::iter() {
+                    ui.spawn((
+                        list.add(format!("{button_type:?}")),
+                        button_type.clone(),
+                        UiLayout::window().y(Rl(offset)).size(Rl((100.0, size))).pack::<Base>(),
+                        MainButton {
+                            text: button_type.label(&settings)
+                        }
+                    ));
+
+                    offset += gap + size;
+                }
+            });
+        });
+    }
+}
+
+#[derive(Component, Debug, Clone, PartialEq)]
+enum SettingsSlider {
+    MasterVolume
+}
+
+#[derive(Component, Debug, Clone, PartialEq, EnumIter)]
+enum SettingsButton {
+    Limiter,
+    Bloom,
+    DisplayMode,
+    Resolution,
+    UiDebug,
+    Back
+}
+impl SettingsButton {
+    fn label(&self, settings: &Settings) -> String {
+        let on_off = |value: bool| if value { "ON" } else { "OFF" };
+
+        match self {
+            SettingsButton::Limiter => format!("FRAME LIMITER  {}", match settings.limiter {
+                LimiterMode::Auto => "AUTO",
+                LimiterMode::Off => "OFF",
+                LimiterMode::Fps30 => "30 FPS",
+                LimiterMode::Fps60 => "60 FPS",
+                LimiterMode::Fps144 => "144 FPS",
+            }),
+            SettingsButton::Bloom => format!("BLOOM  {}", on_off(settings.bloom)),
+            SettingsButton::DisplayMode => format!("DISPLAY  {}", match settings.display_mode {
+                settings::DisplayMode::Windowed => "WINDOWED",
+                settings::DisplayMode::Borderless => "BORDERLESS",
+                settings::DisplayMode::Fullscreen => "FULLSCREEN",
+            }),
+            SettingsButton::Resolution => {
+                let (width, height) = settings.resolution();
+                format!("RESOLUTION  {width}x{height}")
+            },
+            SettingsButton::UiDebug => format!("UI DEBUG  {} (RESTART)", on_off(settings.ui_debug)),
+            SettingsButton::Back => "BACK".into(),
+        }
+    }
+}
+
+fn settings_button_clicked_system(
+    mut commands: Commands,
+    mut events: EventReader<UiClickEvent>,
+    query: Query<&SettingsButton, With<MainButton>>,
+    mut labels: Query<(&SettingsButton, &mut MainButton)>,
+    routes: Query<Entity, With<SettingsRoute>>,
+    mut settings: ResMut<Settings>
+) {
+    for event in events.read() {
+        let Ok(button) = query.get(event.target) else {
+            continue;
+        };
+
+        match button {
+            SettingsButton::Limiter => settings.limiter = settings.limiter.next(),
+            SettingsButton::Bloom => settings.bloom = !settings.bloom,
+            SettingsButton::DisplayMode => settings.display_mode = settings.display_mode.next(),
+            SettingsButton::Resolution => settings.resolution = (settings.resolution + 1) % RESOLUTIONS.len(),
+            SettingsButton::UiDebug => settings.ui_debug = !settings.ui_debug,
+            SettingsButton::Back => {
+                for route in &routes {
+                    commands.entity(route).despawn_recursive();
+                }
+
+                commands.spawn(MainMenuRoute);
+                continue;
+            }
+        }
+
+        for (button_type, mut label) in &mut labels {
+            label.text = button_type.label(&settings);
+        }
+    }
+}
+
+fn settings_slider_changed_system(
+    sliders: Query<(&SettingsSlider, &Slider), Changed<Slider>>,
+    mut settings: ResMut<Settings>
+) {
+    for (slider_type, slider) in &sliders {
+        match slider_type {
+            SettingsSlider::MasterVolume => {
+                if settings.master_volume != slider.value {
+                    settings.master_volume = slider.value;
+                }
+            }
+        }
+    }
+}
+
+fn despawn_settings(
+    mut commands: Commands,
+    routes: Query<Entity, With<SettingsRoute>>
+) {
+    for route in &routes {
+        commands.entity(route).despawn_recursive();
+    }
+}
+
+pub struct SettingsRoutePlugin;
+impl Plugin for SettingsRoutePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(OnExit(GameState::MainMenu), despawn_settings)
+            .add_systems(OnExit(GameState::Paused), despawn_settings)
+            .add_systems(PreUpdate, build_route.before(UiSystems::Compute))
+            .add_systems(Update, settings_slider_changed_system)
+            .add_systems(Update, settings_button_clicked_system
+                .distributive_run_if(on_event::<UiClickEvent>())
+                .distributive_run_if(input_just_pressed(MouseButton::Left)));
+    }
+}
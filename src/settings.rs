@@ -0,0 +1,180 @@
+use std::{fs, io::ErrorKind, path::Path};
+
+use bevy::{audio::Volume, core_pipeline::bloom::BloomSettings, prelude::*, window::{PrimaryWindow, WindowMode}};
+use bevy_framepace::{FramepaceSettings, Limiter};
+use bevy_lunex::prelude::MainUi;
+use serde::{Deserialize, Serialize};
+
+pub const SETTINGS_PATH: &str = "config/settings.ron";
+pub const RESOLUTIONS: [(f32, f32); 4] = [(1280., 720.), (1600., 900.), (1920., 1080.), (2560., 1440.)];
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LimiterMode {
+    #[default]
+    Auto,
+    Off,
+    Fps30,
+    Fps60,
+    Fps144
+}
+
+impl LimiterMode {
+    pub fn next(&self) -> Self {
+        match self {
+            LimiterMode::Auto => LimiterMode::Off,
+            LimiterMode::Off => LimiterMode::Fps30,
+            LimiterMode::Fps30 => LimiterMode::Fps60,
+            LimiterMode::Fps60 => LimiterMode::Fps144,
+            LimiterMode::Fps144 => LimiterMode::Auto,
+        }
+    }
+
+    fn limiter(&self) -> Limiter {
+        match self {
+            LimiterMode::Auto => Limiter::Auto,
+            LimiterMode::Off => Limiter::Off,
+            LimiterMode::Fps30 => Limiter::from_framerate(30.),
+            LimiterMode::Fps60 => Limiter::from_framerate(60.),
+            LimiterMode::Fps144 => Limiter::from_framerate(144.),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DisplayMode {
+    #[default]
+    Windowed,
+    Borderless,
+    Fullscreen
+}
+
+impl DisplayMode {
+    pub fn next(&self) -> Self {
+        match self {
+            DisplayMode::Windowed => DisplayMode::Borderless,
+            DisplayMode::Borderless => DisplayMode::Fullscreen,
+            DisplayMode::Fullscreen => DisplayMode::Windowed,
+        }
+    }
+
+    fn window_mode(&self) -> WindowMode {
+        match self {
+            DisplayMode::Windowed => WindowMode::Windowed,
+            DisplayMode::Borderless => WindowMode::BorderlessFullscreen,
+            DisplayMode::Fullscreen => WindowMode::Fullscreen,
+        }
+    }
+}
+
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub master_volume: f32,
+    pub limiter: LimiterMode,
+    pub bloom: bool,
+    pub display_mode: DisplayMode,
+    pub resolution: usize,
+    /// Only read at startup, the lunex debug plugin can't be removed from a running app.
+    pub ui_debug: bool
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            master_volume: 1.,
+            limiter: LimiterMode::Auto,
+            bloom: true,
+            display_mode: DisplayMode::Windowed,
+            resolution: 0,
+            ui_debug: true
+        }
+    }
+}
+
+impl Settings {
+    /// Falls back to the defaults if the file is missing or can't be read.
+    pub fn load() -> Self {
+        let content = match fs::read_to_string(SETTINGS_PATH) {
+            Ok(content) => content,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Self::default(),
+            Err(err) => {
+                warn!("Could not read {SETTINGS_PATH}: {err}");
+                return Self::default();
+            }
+        };
+
+        ron::from_str(&content).unwrap_or_else(|err| {
+            warn!("Invalid settings in {SETTINGS_PATH}: {err}");
+            Self::default()
+        })
+    }
+
+    pub fn save(&self) {
+        if let Some(dir) = Path::new(SETTINGS_PATH).parent() {
+            if let Err(err) = fs::create_dir_all(dir) {
+                warn!("Could not create {}: {err}", dir.display());
+                return;
+            }
+        }
+
+        let result = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(|err| err.to_string())
+            .and_then(|content| fs::write(SETTINGS_PATH, content).map_err(|err| err.to_string()));
+
+        if let Err(err) = result {
+            warn!("Could not save settings to {SETTINGS_PATH}: {err}");
+        }
+    }
+
+    pub fn resolution(&self) -> (f32, f32) {
+        RESOLUTIONS[self.resolution % RESOLUTIONS.len()]
+    }
+}
+
+fn apply_settings_system(
+    mut commands: Commands,
+    settings: Res<Settings>,
+    mut framepace: ResMut<FramepaceSettings>,
+    mut volume: ResMut<GlobalVolume>,
+    mut windows: Query<&mut Window, With<PrimaryWindow>>,
+    cameras: Query<Entity, (With<MainUi>, With<Camera>)>,
+    mut applied_display: Local<Option<(DisplayMode, usize)>>
+) {
+    framepace.limiter = settings.limiter.limiter();
+    volume.volume = Volume::new(settings.master_volume);
+
+    // Resizing the window resets it even if nothing changed, only touch it when the display settings did
+    let display = (settings.display_mode, settings.resolution);
+    if *applied_display != Some(display) {
+        if let Ok(mut window) = windows.get_single_mut() {
+            let (width, height) = settings.resolution();
+            window.mode = settings.display_mode.window_mode();
+            window.resolution.set(width, height);
+            *applied_display = Some(display);
+        }
+    }
+
+    for camera in &cameras {
+        if settings.bloom {
+            commands.entity(camera).insert(BloomSettings::OLD_SCHOOL);
+        } else {
+            commands.entity(camera).remove::<BloomSettings>();
+        }
+    }
+}
+
+fn save_settings_system(settings: Res<Settings>) {
+    if settings.is_changed() && !settings.is_added() {
+        settings.save();
+    }
+}
+
+pub struct SettingsPlugin;
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, (
+            apply_settings_system.run_if(resource_changed::<Settings>),
+            save_settings_system
+        ));
+    }
+}
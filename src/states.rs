@@ -0,0 +1,31 @@
+use bevy::prelude::*;
+
+#[derive(States, Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GameState {
+    #[default]
+    MainMenu,
+    Loading,
+    InGame,
+    Paused,
+    GameOver,
+    Victory
+}
+
+/// Active while a game session exists, session entities are despawned when it ends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct InSession;
+
+impl ComputedStates for InSession {
+    type SourceStates = GameState;
+
+    fn compute(state: GameState) -> Option<Self> {
+        match state {
+            GameState::InGame | GameState::Paused | GameState::GameOver | GameState::Victory => Some(InSession),
+            GameState::MainMenu | GameState::Loading => None
+        }
+    }
+}
+
+/// Gameplay systems that only run while the game is not paused.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GameplaySet;
@@ -0,0 +1,84 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::components::{enemies::*, turrets::*};
+
+pub fn sentinel_aura_system(
+    mut commands: Commands,
+    changed: Query<(), Or<(Added<BaseStats>, Changed<BaseStats>, Changed<BuffAura>)>>,
+    mut removed: RemovedComponents<Turret>,
+    auras: Query<(Entity, &BuffAura, &Transform)>,
+    mut turrets: Query<(Entity, &Transform, &BaseStats, Option<&mut AttackDelay>, Option<&mut TargetingTurret>, Option<&mut RotationSpeed>), Without<BuffAura>>
+) {
+    let turrets_removed = removed.read().count() > 0;
+    if changed.is_empty() && !turrets_removed {
+        return;
+    }
+
+    for (
+        entity,
+        transform,
+        base_stats,
+        attack_delay,
+        targeting,
+        rotation_speed
+    ) in &mut turrets {
+        let position = transform.translation.truncate();
+        let mut buffed = Buffed::default();
+
+        // Bonuses of the same kind don't stack, only the strongest aura in range counts
+        for (source, aura, aura_transform) in &auras {
+            if aura_transform.translation.truncate().distance(position) > aura.radius {
+                continue;
+            }
+
+            buffed.sources.push(source);
+            buffed.fire_rate_bonus = buffed.fire_rate_bonus.max(aura.fire_rate_bonus);
+            buffed.range_bonus = buffed.range_bonus.max(aura.range_bonus);
+            buffed.rotation_speed_bonus = buffed.rotation_speed_bonus.max(aura.rotation_speed_bonus);
+        }
+
+        if let (Some(mut attack_delay), Some(base)) = (attack_delay, base_stats.attack_delay) {
+            attack_delay.0.set_duration(Duration::from_secs_f32(base / (1. + buffed.fire_rate_bonus)));
+        }
+
+        if let (Some(mut targeting), Some(base)) = (targeting, base_stats.targeting_radius) {
+            targeting.targeting_radius = Some(base * (1. + buffed.range_bonus));
+        }
+
+        if let (Some(mut rotation_speed), Some(base)) = (rotation_speed, base_stats.rotation_speed) {
+            rotation_speed.0 = base * (1. + buffed.rotation_speed_bonus);
+        }
+
+        if buffed.sources.is_empty() {
+            commands.entity(entity).remove::<Buffed>();
+        } else {
+            commands.entity(entity).insert(buffed);
+        }
+    }
+}
+
+pub fn reveal_stealth_system(
+    mut commands: Commands,
+    auras: Query<(&BuffAura, &Transform)>,
+    enemies: Query<(Entity, &Transform, Has<Revealed>), (With<Stealth>, Without<BuffAura>)>
+) {
+    for (entity, transform, revealed) in &enemies {
+        let position = transform.translation.truncate();
+        let detected = auras.iter().any(|(aura, aura_transform)| {
+            aura_transform.translation.truncate().distance(position) <= aura.detection_radius()
+        });
+
+        if detected == revealed {
+            continue;
+        }
+
+        if detected {
+            commands.entity(entity).insert(Revealed);
+        } else {
+            commands.entity(entity).remove::<Revealed>();
+        }
+    }
+}
+
@@ -0,0 +1,75 @@
+use bevy::prelude::*;
+
+use crate::{components::{build::*, economy::*, simulation::{PlayerCommand, SpawnCounter}, turrets::*, upgrades::TurretUpgrades}};
+
+use super::spawn_turret;
+
+pub fn place_turret_system(
+    mut commands: Commands,
+    mut events: EventReader<PlayerCommand>,
+    mut grid: ResMut<BuildGrid>,
+    mut wallet: ResMut<Wallet>,
+    mut spawn_ids: ResMut<SpawnCounter>,
+    current_definitions: Res<CurrentTurretDefinitions>,
+    definitions: Res<Assets<TurretDefinitions>>,
+    mut transactions: EventWriter<Transaction>,
+    mut rejections: EventWriter<BuildRejected>
+) {
+    let Some(definitions) = definitions.get(&current_definitions.0) else {
+        return;
+    };
+
+    for event in events.read() {
+        let PlayerCommand::Build { turret_type, cell } = event else {
+            continue;
+        };
+
+        let pos = grid.cell_center(*cell);
+        let mut reject = |reason| {
+            rejections.send(BuildRejected {
+                turret_type: turret_type.clone(),
+                reason,
+                pos
+            });
+        };
+
+        if !grid.is_buildable(*cell) {
+            reject(RejectReason::InvalidCell);
+            continue;
+        }
+
+        let Some(cost) = definitions.get(turret_type).map(|definition| definition.cost) else {
+            reject(RejectReason::Unavailable);
+            continue;
+        };
+
+        if !wallet.can_afford(cost) {
+            reject(RejectReason::NotEnoughMoney { cost, available: wallet.money });
+            continue;
+        }
+
+        let Some(entity) = spawn_turret(&mut commands, &mut spawn_ids, definitions, turret_type, pos.extend(0.), TargetingPriority::default()) else {
+            reject(RejectReason::Unavailable);
+            continue;
+        };
+
+        wallet.try_spend(cost);
+        commands.entity(entity).insert((Invested(cost), TurretUpgrades::default()));
+        grid.occupy(*cell, entity);
+
+        transactions.send(Transaction {
+            kind: TransactionKind::Build(turret_type.clone()),
+            amount: -(cost as i64),
+            balance: wallet.money
+        });
+    }
+}
+
+pub fn free_build_cells_system(
+    mut removed: RemovedComponents<Turret>,
+    mut grid: ResMut<BuildGrid>
+) {
+    for entity in removed.read() {
+        grid.free(entity);
+    }
+}
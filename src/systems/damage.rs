@@ -0,0 +1,142 @@
+use bevy::prelude::*;
+
+use crate::components::{damage::*, enemies::*, simulation::SpawnId, status_effects::StatusEffects, turrets::TurretStats};
+
+const ARMOR_SCALE: f32 = 10.;
+
+pub fn apply_damage_system(
+    mut commands: Commands,
+    mut events: EventReader<DamageEvent>,
+    mut enemies: Query<(&mut Health, Option<&Armor>, Option<&Resistances>, Option<&StatusEffects>), With<Enemy>>,
+    mut turrets: Query<&mut TurretStats>,
+    spawn_ids: Query<&SpawnId>
+) {
+    // Events arrive in query order, which isn't the same between runs. Health is subtracted as floats
+    // and the last hit claims the kill, so they're applied in spawn order instead.
+    let spawn_id = |entity: Option<Entity>| entity.and_then(|entity| spawn_ids.get(entity).ok().copied());
+    let mut events: Vec<_> = events.read().collect();
+    events.sort_by(|a, b| {
+        spawn_id(Some(a.target)).cmp(&spawn_id(Some(b.target)))
+            .then_with(|| spawn_id(a.source).cmp(&spawn_id(b.source)))
+            .then_with(|| a.damage_type.cmp(&b.damage_type))
+            .then_with(|| a.amount.total_cmp(&b.amount))
+    });
+
+    for event in events {
+        let Ok((mut health, armor, resistances, status_effects)) = enemies.get_mut(event.target) else {
+            continue;
+        };
+
+        if health.is_dead() {
+            continue;
+        }
+
+        let armor_reduction = status_effects.map_or(0., |status_effects| status_effects.armor_reduction());
+        let armor = armor.map_or(0., |armor| (armor.0 - armor_reduction).max(0.));
+        let multiplier = resistances.map_or(1., |resistances| resistances.multiplier(event.damage_type));
+        let mitigated = event.amount * multiplier * ARMOR_SCALE / (ARMOR_SCALE + armor);
+        let dealt = mitigated.min(health.current);
+
+        health.current -= mitigated;
+
+        if let Some(mut stats) = event.source.and_then(|source| turrets.get_mut(source).ok()) {
+            stats.damage_dealt += dealt;
+        }
+
+        if health.is_dead() {
+            commands.entity(event.target).insert(KilledBy(event.source));
+        }
+    }
+}
+
+pub fn credit_kills_system(
+    mut events: EventReader<EnemyKilled>,
+    mut turrets: Query<&mut TurretStats>
+) {
+    for event in events.read() {
+        let Some(mut stats) = event.killer.and_then(|killer| turrets.get_mut(killer).ok()) else {
+            continue;
+        };
+
+        stats.kills += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use bevy::ecs::system::RunSystemOnce;
+
+    use crate::components::status_effects::{StackingRule, StatusEffect, StatusEffectKind};
+
+    use super::*;
+
+    fn world() -> World {
+        let mut world = World::new();
+        world.init_resource::<Events<DamageEvent>>();
+        world
+    }
+
+    fn hit(world: &mut World, target: Entity, amount: f32, damage_type: DamageType) -> f32 {
+        world.send_event(DamageEvent { source: None, target, amount, damage_type });
+        world.run_system_once(apply_damage_system);
+        // A fresh reader would see the same events again on the next run
+        world.resource_mut::<Events<DamageEvent>>().clear();
+        world.get::<Health>(target).unwrap().current
+    }
+
+    #[test]
+    fn unarmored_enemies_take_full_damage() {
+        let mut world = world();
+        let enemy = world.spawn((Enemy(EnemyType::Drone), Health::new(100.))).id();
+
+        assert_eq!(hit(&mut world, enemy, 30., DamageType::Kinetic), 70.);
+    }
+
+    #[test]
+    fn armor_scales_damage_down() {
+        let mut world = world();
+        let enemy = world.spawn((Enemy(EnemyType::Drone), Health::new(100.), Armor(ARMOR_SCALE))).id();
+
+        assert_eq!(hit(&mut world, enemy, 30., DamageType::Kinetic), 85.);
+    }
+
+    #[test]
+    fn resistances_multiply_their_damage_type_only() {
+        let mut world = world();
+        let resistances = Resistances(HashMap::from([(DamageType::Fire, 0.5)]));
+        let enemy = world.spawn((Enemy(EnemyType::Drone), Health::new(100.), resistances)).id();
+
+        assert_eq!(hit(&mut world, enemy, 20., DamageType::Fire), 90.);
+        assert_eq!(hit(&mut world, enemy, 20., DamageType::Kinetic), 70.);
+    }
+
+    #[test]
+    fn corrosion_strips_armor_but_not_below_zero() {
+        let mut world = world();
+        let mut status_effects = StatusEffects::default();
+        status_effects.apply(&StatusEffect {
+            kind: StatusEffectKind::Corrosion { armor_reduction: 50. },
+            duration: 1.,
+            stacking: StackingRule::Refresh
+        }, None);
+        let enemy = world.spawn((Enemy(EnemyType::Drone), Health::new(100.), Armor(ARMOR_SCALE), status_effects)).id();
+
+        assert_eq!(hit(&mut world, enemy, 30., DamageType::Kinetic), 70.);
+    }
+
+    #[test]
+    fn killing_blow_is_credited_once() {
+        let mut world = world();
+        let turret = world.spawn(TurretStats::default()).id();
+        let enemy = world.spawn((Enemy(EnemyType::Drone), Health::new(10.))).id();
+
+        world.send_event(DamageEvent { source: Some(turret), target: enemy, amount: 25., damage_type: DamageType::Kinetic });
+        world.send_event(DamageEvent { source: Some(turret), target: enemy, amount: 25., damage_type: DamageType::Kinetic });
+        world.run_system_once(apply_damage_system);
+
+        assert_eq!(world.get::<TurretStats>(turret).unwrap().damage_dealt, 10., "overkill and hits on the dead aren't counted");
+        assert_eq!(world.get::<KilledBy>(enemy).map(|killed_by| killed_by.0), Some(Some(turret)));
+    }
+}
@@ -0,0 +1,127 @@
+use std::collections::HashSet;
+
+use bevy::prelude::*;
+
+use crate::components::{economy::*, enemies::EnemyKilled, turrets::Turret, waves::WaveStarted};
+
+pub fn reset_wallet_system(
+    mut commands: Commands,
+    settings: Res<EconomySettings>
+) {
+    commands.insert_resource(Wallet::new(settings.starting_money));
+}
+
+pub fn bounty_system(
+    mut events: EventReader<EnemyKilled>,
+    mut wallet: ResMut<Wallet>,
+    mut transactions: EventWriter<Transaction>
+) {
+    for event in events.read() {
+        if event.bounty == 0 {
+            continue;
+        }
+
+        wallet.earn(event.bounty);
+        transactions.send(Transaction {
+            kind: TransactionKind::Bounty(event.enemy_type),
+            amount: event.bounty as i64,
+            balance: wallet.money
+        });
+    }
+}
+
+pub fn early_call_bonus_system(
+    mut events: EventReader<WaveStarted>,
+    mut wallet: ResMut<Wallet>,
+    mut transactions: EventWriter<Transaction>
+) {
+    for event in events.read() {
+        if event.early_call_bonus == 0 {
+            continue;
+        }
+
+        wallet.earn(event.early_call_bonus);
+        transactions.send(Transaction {
+            kind: TransactionKind::EarlyCallBonus,
+            amount: event.early_call_bonus as i64,
+            balance: wallet.money
+        });
+    }
+}
+
+pub fn sell_turret_system(
+    mut commands: Commands,
+    mut events: EventReader<SellTurret>,
+    mut wallet: ResMut<Wallet>,
+    settings: Res<EconomySettings>,
+    turrets: Query<(&Turret, Option<&Invested>)>,
+    mut transactions: EventWriter<Transaction>
+) {
+    // The turret is only despawned once commands are applied, a second sell in the same tick would refund it again
+    let mut sold = HashSet::new();
+
+    for event in events.read() {
+        if !sold.insert(event.0) {
+            continue;
+        }
+
+        let Ok((turret, invested)) = turrets.get(event.0) else {
+            continue;
+        };
+
+        let refund = (invested.map_or(0, |invested| invested.0) as f32 * settings.sell_refund).round() as u32;
+        wallet.earn(refund);
+
+        commands.entity(event.0).despawn_recursive();
+        transactions.send(Transaction {
+            kind: TransactionKind::Sell(turret.0.clone()),
+            amount: refund as i64,
+            balance: wallet.money
+        });
+    }
+}
+
+pub fn log_transactions_system(mut events: EventReader<Transaction>) {
+    for event in events.read() {
+        info!("{:?}: {:+} (balance {})", event.kind, event.amount, event.balance);
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use bevy::math::IVec2;
+
+    use crate::{components::{build::BuildGrid, simulation::PlayerCommand, upgrades::UpgradeTurret, waves::CallNextWave}, systems::dispatch_player_commands_system, TurretType};
+
+    use super::*;
+
+    #[test]
+    fn selling_twice_in_one_tick_refunds_once() {
+        let mut world = World::new();
+        world.init_resource::<Events<PlayerCommand>>();
+        world.init_resource::<Events<SellTurret>>();
+        world.init_resource::<Events<UpgradeTurret>>();
+        world.init_resource::<Events<CallNextWave>>();
+        world.init_resource::<Events<Transaction>>();
+        world.init_resource::<EconomySettings>();
+        world.insert_resource(Wallet::new(0));
+
+        let cell = IVec2::new(3, 4);
+        let turret = world.spawn((Turret(TurretType::PulseBlaster), Invested(100))).id();
+        let mut grid = BuildGrid::default();
+        grid.occupy(cell, turret);
+        world.insert_resource(grid);
+
+        world.send_event(PlayerCommand::Sell { cell });
+        world.send_event(PlayerCommand::Sell { cell });
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems((dispatch_player_commands_system, sell_turret_system).chain());
+        schedule.run(&mut world);
+
+        assert_eq!(world.resource::<Wallet>().money, 70);
+        assert_eq!(world.resource::<Events<Transaction>>().len(), 1);
+        assert!(world.get_entity(turret).is_none());
+    }
+}
@@ -0,0 +1,118 @@
+use bevy::prelude::*;
+
+use crate::{components::{damage::KilledBy, enemies::*, paths::*, simulation::{SpawnCounter, SpawnId}, spatial::EnemyGrid, status_effects::StatusEffects}, enemy_bundles::EnemyBundle, states::InSession};
+
+const ENEMY_Z: f32 = 50.;
+
+pub fn enemy_spawn_system(
+    mut commands: Commands,
+    mut events: EventReader<SpawnEnemy>,
+    mut spawn_ids: ResMut<SpawnCounter>,
+    paths: Res<MapPaths>,
+    current_definitions: Res<CurrentEnemyDefinitions>,
+    definitions: Res<Assets<EnemyDefinitions>>
+) {
+    let Some(definitions) = definitions.get(&current_definitions.0) else {
+        return;
+    };
+
+    for event in events.read() {
+        let Some(lane) = paths.get(event.lane) else {
+            warn!("Cannot spawn {}: lane {} does not exist", event.enemy_type, event.lane);
+            continue;
+        };
+
+        let Some(definition) = definitions.get(event.enemy_type) else {
+            warn!("Cannot spawn {}: no enemy definition", event.enemy_type);
+            continue;
+        };
+
+        spawn_enemy(&mut commands, &mut spawn_ids, event.enemy_type, definition, event.lane, lane.start());
+    }
+}
+
+pub fn spawn_enemy(
+    commands: &mut Commands,
+    spawn_ids: &mut SpawnCounter,
+    enemy_type: EnemyType,
+    definition: &EnemyDefinition,
+    lane: usize,
+    pos: Vec2
+) -> Entity {
+    let mut enemy = commands.spawn((StateScoped(InSession), spawn_ids.assign(), EnemyBundle::new(enemy_type, definition, lane, pos.extend(ENEMY_Z))));
+
+    if definition.stealth {
+        enemy.insert(Stealth);
+    }
+
+    enemy.id()
+}
+
+pub fn rebuild_enemy_grid_system(
+    mut grid: ResMut<EnemyGrid>,
+    enemies: Query<(Entity, &SpawnId, &Transform, &HitRadius), With<Enemy>>
+) {
+    grid.clear();
+
+    let mut enemies: Vec<_> = enemies.iter().collect();
+    enemies.sort_unstable_by_key(|(_, spawn_id, ..)| **spawn_id);
+
+    for (entity, _, transform, hit_radius) in enemies {
+        grid.insert(entity, transform.translation.truncate(), hit_radius.0);
+    }
+}
+
+pub fn enemy_movement_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    paths: Res<MapPaths>,
+    mut enemies: Query<(Entity, &Enemy, &mut Transform, &MovementSpeed, &mut PathFollower, Option<&StatusEffects>)>,
+    mut leaked: EventWriter<EnemyLeaked>
+) {
+    let delta = time.delta_seconds();
+
+    for (entity, enemy, mut transform, speed, mut follower, status_effects) in &mut enemies {
+        let Some(lane) = paths.get(follower.lane) else {
+            continue;
+        };
+
+        let speed_multiplier = status_effects.map_or(1., |status_effects| status_effects.speed_multiplier());
+        follower.distance += speed.0 * speed_multiplier * delta;
+
+        if follower.distance >= lane.length() {
+            leaked.send(EnemyLeaked {
+                entity,
+                enemy_type: enemy.0
+            });
+
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        let position = lane.sample(follower.distance);
+        transform.translation.x = position.x;
+        transform.translation.y = position.y;
+    }
+}
+
+pub fn enemy_death_system(
+    mut commands: Commands,
+    enemies: Query<(Entity, &Enemy, &Health, &Transform, &Bounty, Option<&KilledBy>)>,
+    mut killed: EventWriter<EnemyKilled>
+) {
+    for (entity, enemy, health, transform, bounty, killed_by) in &enemies {
+        if !health.is_dead() {
+            continue;
+        }
+
+        killed.send(EnemyKilled {
+            entity,
+            killer: killed_by.and_then(|killed_by| killed_by.0),
+            enemy_type: enemy.0,
+            pos: transform.translation,
+            bounty: bounty.0
+        });
+
+        commands.entity(entity).despawn();
+    }
+}
@@ -1 +1,199 @@
-pub mod turrets;
\ No newline at end of file
+pub mod turrets;
+pub use turrets::*;
+
+pub mod enemies;
+pub use enemies::*;
+
+pub mod paths;
+pub use paths::*;
+
+pub mod waves;
+pub use waves::*;
+
+pub mod damage;
+pub use damage::*;
+
+pub mod status_effects;
+pub use status_effects::*;
+
+pub mod auras;
+pub use auras::*;
+
+pub mod build;
+pub use build::*;
+
+pub mod economy;
+pub use economy::*;
+
+pub mod upgrades;
+pub use upgrades::*;
+
+pub mod session;
+pub use session::*;
+
+pub mod save;
+pub use save::*;
+
+pub mod selection;
+pub use selection::*;
+
+pub mod simulation;
+pub use simulation::*;
+
+pub mod replay;
+pub use replay::*;
+
+use bevy::prelude::*;
+use bevy_common_assets::ron::RonAssetPlugin;
+
+use crate::{components::{build::BuildGrid, damage::DamageEvent, economy::*, enemies::*, paths::*, replay::*, save::PendingLoad, session::{LoadingFailed, Lives, SessionStats}, simulation::*, spatial::EnemyGrid, status_effects::ApplyStatusEffect, turrets::TurretDefinitions, upgrades::*, waves::*}, states::*};
+
+/// Plugin adding the gameplay simulation, runs without a window
+pub struct GameplayPlugin;
+impl Plugin for GameplayPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_plugins(RonAssetPlugin::<GameMap>::new(&["map.ron"]))
+            .add_plugins(RonAssetPlugin::<WaveSet>::new(&["waves.ron"]))
+            .add_plugins(RonAssetPlugin::<EnemyDefinitions>::new(&["enemies.ron"]))
+            .add_plugins(RonAssetPlugin::<UpgradeTrees>::new(&["upgrades.ron"]))
+            .add_plugins(RonAssetPlugin::<TurretDefinitions>::new(&["turrets.ron"]))
+            .insert_resource(Time::<Fixed>::from_hz(SIMULATION_HZ))
+            .init_resource::<MapPaths>()
+            .init_resource::<ArenaBounds>()
+            .init_resource::<WaveSpawner>()
+            .init_resource::<BuildGrid>()
+            .init_resource::<EconomySettings>()
+            .init_resource::<Lives>()
+            .init_resource::<SessionStats>()
+            .init_resource::<SessionSeed>()
+            .init_resource::<SimulationTick>()
+            .init_resource::<SpawnCounter>()
+            .init_resource::<EnemyGrid>()
+            .add_event::<SpawnEnemy>()
+            .add_event::<EnemyKilled>()
+            .add_event::<EnemyLeaked>()
+            .add_event::<DamageEvent>()
+            .add_event::<ApplyStatusEffect>()
+            .add_event::<Transaction>()
+            .add_event::<BuildRejected>()
+            .add_event::<SellTurret>()
+            .add_event::<UpgradeTurret>()
+            .add_event::<CallNextWave>()
+            .add_event::<WaveStarted>()
+            .add_event::<WaveCleared>()
+            .add_event::<PlayerCommand>()
+            .add_event::<LoadingFailed>()
+            .add_computed_state::<InSession>()
+            .enable_state_scoped_entities::<InSession>()
+            .configure_sets(FixedUpdate, GameplaySet.run_if(in_state(GameState::InGame)))
+            .add_systems(OnEnter(GameState::Loading), (reset_session_system, reset_wallet_system, seed_session_system, reset_game_speed_system))
+            .add_systems(OnEnter(GameState::MainMenu), reset_game_speed_system)
+            .add_systems(OnEnter(GameState::InGame), restore_save_system)
+            .add_systems(Update, apply_map_system)
+            .add_systems(Update, loading_system.run_if(in_state(GameState::Loading)))
+            .add_systems(FixedUpdate, (
+                tick_simulation_system,
+                dispatch_player_commands_system,
+                place_turret_system
+            ).chain().before(wave_spawner_system).before(sell_turret_system).before(reload_turret_definitions_system).in_set(GameplaySet))
+            .add_systems(FixedUpdate, game_speed_system.in_set(GameplaySet))
+            .add_systems(FixedUpdate, victory_system.in_set(GameplaySet))
+            .add_systems(FixedUpdate, lose_lives_system.after(enemy_movement_system).in_set(GameplaySet))
+            .add_systems(FixedUpdate, session_stats_system.after(enemy_movement_system).after(bounty_system).after(wave_spawner_system).in_set(GameplaySet))
+            .add_systems(FixedUpdate, free_build_cells_system.after(sell_turret_system).in_set(GameplaySet))
+            .add_systems(FixedUpdate, (
+                bounty_system.after(enemy_death_system),
+                early_call_bonus_system.after(wave_spawner_system),
+                sell_turret_system
+            ).in_set(GameplaySet))
+            .add_systems(FixedUpdate, (
+                sentinel_aura_system,
+                reveal_stealth_system
+            ).chain().before(turret_targeting_system).in_set(GameplaySet))
+            .add_systems(FixedUpdate, (
+                reload_turret_definitions_system,
+                replay_upgrades_system,
+                upgrade_turret_system
+            ).chain().before(sentinel_aura_system).in_set(GameplaySet))
+            .add_systems(FixedUpdate, wave_spawner_system.before(enemy_spawn_system).in_set(GameplaySet))
+            // Hit and range checks see the enemies where they were at the start of the tick
+            .add_systems(FixedUpdate, rebuild_enemy_grid_system
+                .before(enemy_movement_system)
+                .before(projectile_system)
+                .before(homing_projectile_system)
+                .before(explosion_spawn_system)
+                .before(aoe_turret_attack_system)
+                .before(aoe_attack_damage_system)
+                .before(turret_targeting_system)
+                .before(rail_gun_attack_system)
+                .before(chain_lightning_attack_system)
+                .in_set(GameplaySet))
+            .add_systems(FixedUpdate, (
+                enemy_spawn_system,
+                // Enemies killed this tick are gone before they can reach the exit and cost a life too
+                enemy_movement_system.after(enemy_death_system),
+                projectile_system,
+                decaying_projectile_system,
+                homing_projectile_system,
+                explosion_spawn_system,
+                aoe_animation_system,
+                aoe_turret_attack_system,
+                turret_targeting_system,
+                projectile_turret_attack_system,
+                flag_idle_turrets,
+                idle_rotation_system,
+                aoe_attack_damage_system,
+                continuous_damage_system,
+                laser_turret_attack_system.after(turret_targeting_system).before(continuous_damage_system),
+                orphaned_laser_beam_system,
+                rail_gun_attack_system.after(turret_targeting_system),
+                chain_lightning_attack_system.after(turret_targeting_system),
+                lightning_arc_system.after(chain_lightning_attack_system)
+            ).in_set(GameplaySet))
+            .add_systems(FixedUpdate, (
+                apply_status_effects_system,
+                status_effect_tick_system
+            ).chain().before(enemy_movement_system).after(projectile_system).after(aoe_attack_damage_system).after(chain_lightning_attack_system).in_set(GameplaySet))
+            .add_systems(FixedUpdate, (
+                apply_damage_system,
+                enemy_death_system,
+                credit_kills_system
+            ).chain().after(projectile_system).after(explosion_spawn_system).after(aoe_attack_damage_system).after(continuous_damage_system).after(status_effect_tick_system).after(rail_gun_attack_system).after(chain_lightning_attack_system).in_set(GameplaySet));
+    }
+}
+
+/// Plugin recording every session to a replay file and playing replays back
+pub struct ReplayPlugin;
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .configure_sets(FixedUpdate, GameplaySet.run_if(replay_step_allowed))
+            .add_systems(OnEnter(GameState::Loading), (
+                prepare_playback_system.run_if(resource_exists::<ReplayPlayback>).before(seed_session_system),
+                restore_replaced_assets_system
+                    .run_if(resource_exists::<ReplacedAssets>)
+                    .run_if(not(resource_exists::<ReplayPlayback>))
+                    .before(start_recording_system),
+                start_recording_system.run_if(not(resource_exists::<ReplayPlayback>)).after(seed_session_system)
+            ))
+            .add_systems(OnEnter(GameState::InGame), discard_recording_system.run_if(resource_exists::<PendingLoad>).before(restore_save_system))
+            .add_systems(OnEnter(GameState::GameOver), write_replay_system)
+            .add_systems(OnEnter(GameState::Victory), write_replay_system)
+            .add_systems(OnExit(InSession), write_replay_system)
+            .add_systems(OnEnter(GameState::MainMenu), (
+                stop_playback_system,
+                restore_replaced_assets_system.run_if(resource_exists::<ReplacedAssets>)
+            ))
+            .add_systems(FixedUpdate, playback_commands_system
+                .run_if(resource_exists::<ReplayPlayback>)
+                .after(tick_simulation_system)
+                .before(dispatch_player_commands_system)
+                .in_set(GameplaySet))
+            .add_systems(FixedUpdate, (
+                record_commands_system.after(tick_simulation_system),
+                record_wave_starts_system.after(wave_spawner_system)
+            ).run_if(resource_exists::<ReplayRecorder>).in_set(GameplaySet))
+            .add_systems(Update, playback_speed_system.run_if(resource_exists::<ReplayPlayback>));
+    }
+}
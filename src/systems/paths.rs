@@ -0,0 +1,36 @@
+use bevy::prelude::*;
+
+use crate::components::{build::BuildGrid, paths::*};
+
+pub fn apply_map_system(
+    mut events: EventReader<AssetEvent<GameMap>>,
+    current_map: Res<CurrentMap>,
+    maps: Res<Assets<GameMap>>,
+    mut paths: ResMut<MapPaths>,
+    mut grid: ResMut<BuildGrid>,
+    mut bounds: ResMut<ArenaBounds>
+) {
+    for event in events.read() {
+        let (AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id }) = event else {
+            continue;
+        };
+
+        if *id != current_map.0.id() {
+            continue;
+        }
+
+        if let Some(map) = maps.get(*id) {
+            *paths = MapPaths::from_map(map);
+            info!("Loaded {} lanes", paths.lanes.len());
+
+            let occupied = std::mem::take(&mut grid.occupied);
+            *grid = BuildGrid::new(&map.build_grid, &paths.lanes);
+            grid.occupied = occupied;
+
+            bounds.0 = paths.lanes.iter()
+                .flat_map(|lane| lane.points.iter())
+                .fold(grid.bounds(), |bounds, point| bounds.union_point(*point));
+        }
+    }
+}
+
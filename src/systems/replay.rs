@@ -0,0 +1,195 @@
+use std::{fs, io::ErrorKind, path::Path};
+
+use bevy::{asset::AssetPath, prelude::*};
+
+use crate::components::{paths::CurrentMap, replay::*, simulation::*, waves::{CurrentWaves, WaveStarted}};
+
+pub fn load_replay() -> Result<Replay, ReplayError> {
+    let content = fs::read_to_string(REPLAY_PATH).map_err(|err| match err.kind() {
+        ErrorKind::NotFound => ReplayError::Missing,
+        _ => ReplayError::Io(err)
+    })?;
+
+    let header: ReplayHeader = ron::from_str(&content).map_err(|err| ReplayError::Corrupt(err.to_string()))?;
+    if header.version != REPLAY_VERSION {
+        return Err(ReplayError::Outdated { found: header.version, expected: REPLAY_VERSION });
+    }
+
+    ron::from_str(&content).map_err(|err| ReplayError::Corrupt(err.to_string()))
+}
+
+pub fn write_replay(replay: &Replay) -> Result<(), ReplayError> {
+    if let Some(dir) = Path::new(REPLAY_PATH).parent() {
+        fs::create_dir_all(dir).map_err(ReplayError::Io)?;
+    }
+
+    let content = ron::ser::to_string_pretty(replay, ron::ser::PrettyConfig::default())
+        .map_err(|err| ReplayError::Corrupt(err.to_string()))?;
+
+    fs::write(REPLAY_PATH, content).map_err(ReplayError::Io)
+}
+
+/// Player input only reaches the simulation while no replay is played back.
+pub fn accepts_player_input(playback: Option<Res<ReplayPlayback>>) -> bool {
+    playback.is_none()
+}
+
+/// Holds the simulation while playback is paused or has reached the end of the recording,
+/// and lets it run until the target tick while seeking.
+pub fn replay_step_allowed(
+    playback: Option<Res<ReplayPlayback>>,
+    tick: Res<SimulationTick>
+) -> bool {
+    let Some(playback) = playback else {
+        return true;
+    };
+
+    if tick.0 >= playback.replay.end {
+        return false;
+    }
+
+    match playback.seek_to {
+        Some(target) => tick.0 < target,
+        None => !playback.paused
+    }
+}
+
+/// Every session of a replay starts from its seed, so restarting the session restarts the replay.
+pub fn prepare_playback_system(
+    mut commands: Commands,
+    mut playback: ResMut<ReplayPlayback>,
+    mut current_map: ResMut<CurrentMap>,
+    mut current_waves: ResMut<CurrentWaves>,
+    replaced: Option<Res<ReplacedAssets>>,
+    asset_server: Res<AssetServer>
+) {
+    playback.next = 0;
+    commands.insert_resource(RequestedSeed(playback.replay.seed));
+
+    // Seeking back loads the replay again, by then the handles are already the replay's own
+    if replaced.is_none() {
+        commands.insert_resource(ReplacedAssets {
+            map: current_map.0.clone(),
+            waves: current_waves.0.clone()
+        });
+    }
+
+    current_map.0 = asset_server.load(&playback.replay.map);
+    current_waves.0 = asset_server.load(&playback.replay.waves);
+}
+
+pub fn start_recording_system(
+    mut commands: Commands,
+    seed: Res<SessionSeed>,
+    current_map: Res<CurrentMap>,
+    current_waves: Res<CurrentWaves>
+) {
+    let path = |path: Option<&AssetPath>| path.map_or(String::new(), |path| path.to_string());
+
+    commands.insert_resource(ReplayRecorder(Replay {
+        version: REPLAY_VERSION,
+        seed: seed.0,
+        map: path(current_map.0.path()),
+        waves: path(current_waves.0.path()),
+        commands: Vec::new(),
+        wave_starts: Vec::new(),
+        end: 0
+    }));
+}
+
+/// A restored save didn't start from the seed, so replaying its commands would play out differently.
+pub fn discard_recording_system(mut commands: Commands) {
+    commands.remove_resource::<ReplayRecorder>();
+    info!("Sessions continued from a save are not recorded");
+}
+
+pub fn record_commands_system(
+    mut events: EventReader<PlayerCommand>,
+    tick: Res<SimulationTick>,
+    mut recorder: ResMut<ReplayRecorder>
+) {
+    recorder.0.commands.extend(events.read().map(|command| RecordedCommand {
+        tick: tick.0,
+        command: command.clone()
+    }));
+}
+
+pub fn record_wave_starts_system(
+    mut events: EventReader<WaveStarted>,
+    tick: Res<SimulationTick>,
+    mut recorder: ResMut<ReplayRecorder>
+) {
+    for _ in events.read() {
+        recorder.0.wave_starts.push(tick.0);
+    }
+}
+
+pub fn write_replay_system(
+    mut commands: Commands,
+    recorder: Option<Res<ReplayRecorder>>,
+    tick: Res<SimulationTick>
+) {
+    let Some(recorder) = recorder else {
+        return;
+    };
+
+    commands.remove_resource::<ReplayRecorder>();
+
+    let mut replay = recorder.0.clone();
+    replay.end = tick.0;
+
+    match write_replay(&replay) {
+        Ok(()) => info!("Replay saved to {REPLAY_PATH}"),
+        Err(err) => error!("Saving replay failed: {err}")
+    }
+}
+
+/// Sends the commands recorded for this tick, before they are dispatched.
+pub fn playback_commands_system(
+    mut playback: ResMut<ReplayPlayback>,
+    tick: Res<SimulationTick>,
+    mut commands: EventWriter<PlayerCommand>
+) {
+    let playback = &mut *playback;
+
+    while let Some(recorded) = playback.replay.commands.get(playback.next).filter(|recorded| recorded.tick <= tick.0) {
+        commands.send(recorded.command.clone());
+        playback.next += 1;
+    }
+}
+
+/// Playback ignores the recorded game speed and runs at the viewer's.
+pub fn playback_speed_system(
+    mut playback: ResMut<ReplayPlayback>,
+    tick: Res<SimulationTick>,
+    mut time: ResMut<Time<Virtual>>
+) {
+    if playback.seek_to.is_some_and(|target| tick.0 >= target) {
+        playback.seek_to = None;
+    }
+
+    if tick.0 >= playback.replay.end && !playback.paused {
+        playback.paused = true;
+        playback.seek_to = None;
+    }
+
+    let speed = if playback.seek_to.is_some() { SEEK_SPEED } else { playback.speed };
+    if time.relative_speed() != speed {
+        time.set_relative_speed(speed);
+    }
+}
+
+pub fn stop_playback_system(mut commands: Commands) {
+    commands.remove_resource::<ReplayPlayback>();
+}
+
+pub fn restore_replaced_assets_system(
+    mut commands: Commands,
+    replaced: Res<ReplacedAssets>,
+    mut current_map: ResMut<CurrentMap>,
+    mut current_waves: ResMut<CurrentWaves>
+) {
+    current_map.0 = replaced.map.clone();
+    current_waves.0 = replaced.waves.clone();
+    commands.remove_resource::<ReplacedAssets>();
+}
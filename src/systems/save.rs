@@ -0,0 +1,475 @@
+use std::{collections::HashMap, fs, io::ErrorKind, path::Path, time::Duration};
+
+use bevy::prelude::*;
+use bevy_prng::ChaCha8Rng;
+use bevy_rand::prelude::GlobalEntropy;
+
+use crate::{
+    components::{build::BuildGrid, damage::*, economy::*, enemies::*, paths::*, save::*, session::{Lives, SessionStats}, simulation::*, status_effects::*, turrets::*, upgrades::*, waves::*},
+    states::InSession
+};
+
+use super::{seed_rng, spawn_enemy, spawn_turret};
+
+pub fn load_save_game() -> Result<SaveGame, SaveError> {
+    load_save_game_from(Path::new(SAVE_PATH))
+}
+
+pub fn load_save_game_from(path: &Path) -> Result<SaveGame, SaveError> {
+    let content = fs::read_to_string(path).map_err(|err| match err.kind() {
+        ErrorKind::NotFound => SaveError::Missing,
+        _ => SaveError::Io(err)
+    })?;
+
+    let header: SaveHeader = ron::from_str(&content).map_err(|err| SaveError::Corrupt(err.to_string()))?;
+    if header.version != SAVE_VERSION {
+        return Err(SaveError::Outdated { found: header.version, expected: SAVE_VERSION });
+    }
+
+    let save: SaveGame = ron::from_str(&content).map_err(|err| SaveError::Corrupt(err.to_string()))?;
+    check_durations(&save)?;
+
+    Ok(save)
+}
+
+/// Timers panic on negative or NaN durations, which a hand-edited save could contain.
+fn check_durations(save: &SaveGame) -> Result<(), SaveError> {
+    let mut durations = Vec::new();
+
+    match &save.phase {
+        SavedWavePhase::Build { remaining } => durations.push(("build time", *remaining)),
+        SavedWavePhase::Spawning { groups } => {
+            for group in groups {
+                durations.extend([("spawn spacing", group.spacing), ("spawn timer", group.timer_duration), ("spawn timer", group.timer_elapsed)]);
+            }
+        },
+        SavedWavePhase::AwaitingClear | SavedWavePhase::Finished => ()
+    }
+
+    for saved in save.enemies.iter().flat_map(|enemy| &enemy.status_effects) {
+        durations.extend([("status effect", saved.effect.duration), ("status effect", saved.elapsed)]);
+    }
+
+    for effect in save.projectiles.iter().filter_map(|projectile| projectile.on_hit_effect.as_ref()) {
+        durations.push(("on-hit effect", effect.duration));
+    }
+
+    match durations.into_iter().find(|(_, duration)| !duration.is_finite() || *duration < 0.) {
+        Some((name, duration)) => Err(SaveError::Corrupt(format!("invalid {name} duration {duration}"))),
+        None => Ok(())
+    }
+}
+
+pub fn write_save_game(save: &SaveGame) -> Result<(), SaveError> {
+    write_save_game_to(Path::new(SAVE_PATH), save)
+}
+
+pub fn write_save_game_to(path: &Path, save: &SaveGame) -> Result<(), SaveError> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(SaveError::Io)?;
+    }
+
+    let content = ron::ser::to_string_pretty(save, ron::ser::PrettyConfig::default())
+        .map_err(|err| SaveError::Serialize(err.to_string()))?;
+
+    fs::write(path, content).map_err(SaveError::Io)
+}
+
+pub fn save_game_system(
+    spawner: Res<WaveSpawner>,
+    wallet: Res<Wallet>,
+    lives: Res<Lives>,
+    stats: Res<SessionStats>,
+    seed: Res<SessionSeed>,
+    tick: Res<SimulationTick>,
+    turrets: Query<(Entity, &Turret, &Transform, Option<&Interpolated>, &TurretStats, Option<&TargetingTurret>, Option<&TurretUpgrades>, Option<&Invested>)>,
+    enemies: Query<(&Enemy, &Health, &PathFollower, Option<&StatusEffects>)>,
+    projectiles: Query<(&Transform, Option<&Interpolated>, &InstantDamage, &LinearVelocity, Option<&DamageKind>, Option<&FiredBy>, Option<&Homing>, Option<&Explosive>, Option<&OnHitEffect>), With<Projectile>>
+) {
+    let phase = match &spawner.phase {
+        WavePhase::Loading => SavedWavePhase::Build { remaining: 0. },
+        WavePhase::Build(timer) => SavedWavePhase::Build { remaining: timer.remaining_secs() },
+        WavePhase::Spawning => SavedWavePhase::Spawning {
+            groups: spawner.groups.iter()
+                .map(|group| SavedGroup {
+                    enemy: group.enemy,
+                    lane: group.lane,
+                    remaining: group.remaining,
+                    spacing: group.spacing.as_secs_f32(),
+                    timer_duration: group.timer.duration().as_secs_f32(),
+                    timer_elapsed: group.timer.elapsed_secs()
+                })
+                .collect()
+        },
+        WavePhase::AwaitingClear => SavedWavePhase::AwaitingClear,
+        WavePhase::Finished => SavedWavePhase::Finished
+    };
+
+    let mut turret_indices = HashMap::new();
+    let saved_turrets = turrets.iter()
+        .enumerate()
+        .map(|(index, (entity, turret, transform, interpolated, stats, targeting, upgrades, invested))| {
+            turret_indices.insert(entity, index);
+
+            SavedTurret {
+                turret_type: turret.0.clone(),
+                position: transform.translation.truncate(),
+                rotation: interpolated.map_or(transform.rotation, |interpolated| interpolated.current_rotation).to_euler(EulerRot::XYZ).2,
+                upgrades: upgrades.map_or(Vec::new(), |upgrades| upgrades.tiers.clone()),
+                priority: targeting.map_or(TargetingPriority::default(), |targeting| targeting.priority),
+                invested: invested.map_or(0, |invested| invested.0),
+                damage_dealt: stats.damage_dealt,
+                kills: stats.kills
+            }
+        })
+        .collect();
+
+    let saved_enemies = enemies.iter()
+        .map(|(enemy, health, follower, status_effects)| SavedEnemy {
+            enemy_type: enemy.0,
+            lane: follower.lane,
+            distance: follower.distance,
+            health: health.current,
+            status_effects: status_effects.map_or(Vec::new(), |status_effects| status_effects.active.iter()
+                .map(|active| SavedStatusEffect {
+                    effect: active.effect.clone(),
+                    stacks: active.stacks,
+                    elapsed: active.timer.elapsed_secs(),
+                    source: active.source.and_then(|source| turret_indices.get(&source).copied())
+                })
+                .collect())
+        })
+        .collect();
+
+    let saved_projectiles = projectiles.iter()
+        .map(|(transform, interpolated, damage, velocity, damage_kind, fired_by, homing, explosive, on_hit_effect)| SavedProjectile {
+            position: interpolated.map_or(transform.translation, |interpolated| interpolated.current).truncate(),
+            rotation: interpolated.map_or(transform.rotation, |interpolated| interpolated.current_rotation).to_euler(EulerRot::XYZ).2,
+            damage: damage.0,
+            speed: velocity.0,
+            damage_type: damage_kind.map_or(DamageType::default(), |kind| kind.0),
+            fired_by: fired_by.and_then(|fired_by| turret_indices.get(&fired_by.0).copied()),
+            homing: homing.cloned(),
+            explosive: explosive.cloned(),
+            on_hit_effect: on_hit_effect.map(|effect| effect.0.clone())
+        })
+        .collect();
+
+    let save = SaveGame {
+        version: SAVE_VERSION,
+        seed: seed.0,
+        tick: tick.0,
+        wave_index: spawner.wave_index,
+        phase,
+        money: wallet.money,
+        lives: lives.0,
+        turrets: saved_turrets,
+        enemies: saved_enemies,
+        projectiles: saved_projectiles,
+        stats: stats.clone()
+    };
+
+    match write_save_game(&save) {
+        Ok(()) => info!("Game saved to {SAVE_PATH}"),
+        Err(err) => error!("Saving failed: {err}")
+    }
+}
+
+pub fn restore_save_system(
+    mut commands: Commands,
+    pending: Option<Res<PendingLoad>>,
+    current_turrets: Res<CurrentTurretDefinitions>,
+    turret_definitions: Res<Assets<TurretDefinitions>>,
+    current_enemies: Res<CurrentEnemyDefinitions>,
+    enemy_definitions: Res<Assets<EnemyDefinitions>>,
+    paths: Res<MapPaths>,
+    mut spawner: ResMut<WaveSpawner>,
+    mut wallet: ResMut<Wallet>,
+    mut lives: ResMut<Lives>,
+    mut stats: ResMut<SessionStats>,
+    mut rng: ResMut<GlobalEntropy<ChaCha8Rng>>,
+    mut grid: ResMut<BuildGrid>,
+    mut spawn_ids: ResMut<SpawnCounter>
+) {
+    let Some(pending) = pending else {
+        return;
+    };
+
+    commands.remove_resource::<PendingLoad>();
+
+    let (Some(turret_definitions), Some(enemy_definitions)) = (turret_definitions.get(&current_turrets.0), enemy_definitions.get(&current_enemies.0)) else {
+        error!("Cannot restore save: definitions are not loaded");
+        return;
+    };
+
+    let save = &pending.0;
+
+    spawner.wave_index = save.wave_index;
+    spawner.groups.clear();
+    spawner.phase = match &save.phase {
+        SavedWavePhase::Build { remaining } => WavePhase::Build(Timer::from_seconds(*remaining, TimerMode::Once)),
+        SavedWavePhase::Spawning { groups } => {
+            spawner.groups = groups.iter()
+                .map(|group| {
+                    let mut timer = Timer::from_seconds(group.timer_duration, TimerMode::Once);
+                    timer.set_elapsed(Duration::from_secs_f32(group.timer_elapsed));
+
+                    GroupSpawner {
+                        enemy: group.enemy,
+                        lane: group.lane,
+                        remaining: group.remaining,
+                        spacing: Duration::from_secs_f32(group.spacing),
+                        timer
+                    }
+                })
+                .collect();
+
+            WavePhase::Spawning
+        },
+        SavedWavePhase::AwaitingClear => WavePhase::AwaitingClear,
+        SavedWavePhase::Finished => WavePhase::Finished
+    };
+
+    wallet.money = save.money;
+    lives.0 = save.lives;
+    *stats = save.stats.clone();
+
+    // The generator state isn't saved, derive a new one that is still the same for every load of this save
+    seed_rng(&mut rng, save.seed ^ save.tick);
+    commands.insert_resource(SessionSeed(save.seed));
+    commands.insert_resource(SimulationTick(save.tick));
+
+    let mut turrets = Vec::with_capacity(save.turrets.len());
+    for saved in &save.turrets {
+        let Some(entity) = spawn_turret(&mut commands, &mut spawn_ids, turret_definitions, &saved.turret_type, saved.position.extend(0.), saved.priority) else {
+            warn!("Cannot restore {}: no turret definition", saved.turret_type);
+            turrets.push(None);
+            continue;
+        };
+
+        commands.entity(entity).insert((
+            Transform {
+                translation: saved.position.extend(0.),
+                rotation: Quat::from_rotation_z(saved.rotation),
+                ..default()
+            },
+            TurretStats {
+                damage_dealt: saved.damage_dealt,
+                kills: saved.kills
+            },
+            Invested(saved.invested),
+            TurretUpgrades { tiers: saved.upgrades.clone() },
+            ReplayUpgrades
+        ));
+
+        if let Some(cell) = grid.cell_at(saved.position) {
+            grid.occupy(cell, entity);
+        }
+
+        turrets.push(Some(entity));
+    }
+
+    for saved in &save.enemies {
+        let (Some(definition), Some(lane)) = (enemy_definitions.get(saved.enemy_type), paths.get(saved.lane)) else {
+            warn!("Cannot restore {} on lane {}", saved.enemy_type, saved.lane);
+            continue;
+        };
+
+        let entity = spawn_enemy(&mut commands, &mut spawn_ids, saved.enemy_type, definition, saved.lane, lane.sample(saved.distance));
+        commands.entity(entity).insert((
+            Health {
+                current: saved.health,
+                max: definition.health
+            },
+            PathFollower {
+                lane: saved.lane,
+                distance: saved.distance
+            },
+            StatusEffects {
+                active: saved.status_effects.iter()
+                    .map(|saved| {
+                        let mut timer = Timer::from_seconds(saved.effect.duration, TimerMode::Once);
+                        timer.set_elapsed(Duration::from_secs_f32(saved.elapsed));
+
+                        ActiveEffect {
+                            effect: saved.effect.clone(),
+                            stacks: saved.stacks,
+                            timer,
+                            source: saved.source.and_then(|index| turrets.get(index).copied().flatten())
+                        }
+                    })
+                    .collect()
+            }
+        ));
+    }
+
+    for saved in &save.projectiles {
+        let mut projectile = commands.spawn((
+            StateScoped(InSession),
+            Projectile {
+                auto_despawn: true,
+                radius: 1.
+            },
+            InstantDamage(saved.damage),
+            LinearVelocity(saved.speed),
+            DamageKind(saved.damage_type),
+            TransformBundle::from_transform(Transform {
+                translation: saved.position.extend(0.),
+                rotation: Quat::from_rotation_z(saved.rotation),
+                ..default()
+            })
+        ));
+
+        if let Some(turret) = saved.fired_by.and_then(|index| turrets.get(index).copied().flatten()) {
+            projectile.insert(FiredBy(turret));
+        }
+
+        if let Some(homing) = &saved.homing {
+            projectile.insert(homing.clone());
+        }
+
+        if let Some(explosive) = &saved.explosive {
+            projectile.insert(explosive.clone());
+        }
+
+        if let Some(effect) = &saved.on_hit_effect {
+            projectile.insert(OnHitEffect(effect.clone()));
+        }
+    }
+
+    info!("Restored wave {} with {} turrets and {} enemies", save.wave_index + 1, save.turrets.len(), save.enemies.len());
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::TurretType;
+
+    use super::*;
+
+    /// File in the temp directory, removed again when dropped.
+    struct TempSave(PathBuf);
+
+    impl TempSave {
+        fn new(name: &str) -> Self {
+            Self(std::env::temp_dir().join(format!("bevy_game_{}_{name}.save.ron", std::process::id())))
+        }
+
+        fn write(name: &str, content: &str) -> Self {
+            let file = Self::new(name);
+            fs::write(&file.0, content).unwrap();
+            file
+        }
+    }
+
+    impl Drop for TempSave {
+        fn drop(&mut self) {
+            let _ = fs::remove_file(&self.0);
+        }
+    }
+
+    fn save() -> SaveGame {
+        let slow = StatusEffect {
+            kind: StatusEffectKind::Slow { factor: 0.5 },
+            duration: 2.,
+            stacking: StackingRule::Refresh
+        };
+
+        SaveGame {
+            version: SAVE_VERSION,
+            seed: 42,
+            tick: 1200,
+            wave_index: 3,
+            phase: SavedWavePhase::Spawning {
+                groups: vec![SavedGroup { enemy: EnemyType::Runner, lane: 1, remaining: 4, spacing: 0.5, timer_duration: 0.5, timer_elapsed: 0.25 }]
+            },
+            money: 310,
+            lives: 17,
+            turrets: vec![SavedTurret {
+                turret_type: TurretType::CryoGenerator,
+                position: Vec2::new(96., -32.),
+                rotation: 1.5,
+                upgrades: vec![0, 0],
+                priority: TargetingPriority::Strongest,
+                invested: 240,
+                damage_dealt: 812.5,
+                kills: 9
+            }],
+            enemies: vec![SavedEnemy {
+                enemy_type: EnemyType::Juggernaut,
+                lane: 0,
+                distance: 321.,
+                health: 77.,
+                status_effects: vec![SavedStatusEffect { effect: slow.clone(), stacks: 1, elapsed: 0.5, source: Some(0) }]
+            }],
+            projectiles: vec![SavedProjectile {
+                position: Vec2::new(10., 20.),
+                rotation: 0.25,
+                damage: 12.,
+                speed: 400.,
+                damage_type: DamageType::Cold,
+                fired_by: Some(0),
+                homing: None,
+                explosive: None,
+                on_hit_effect: Some(slow)
+            }],
+            stats: SessionStats::default()
+        }
+    }
+
+    fn to_ron(save: &SaveGame) -> String {
+        ron::ser::to_string_pretty(save, ron::ser::PrettyConfig::default()).unwrap()
+    }
+
+    #[test]
+    fn saves_load_back_unchanged() {
+        let file = TempSave::new("round_trip");
+        let save = save();
+
+        write_save_game_to(&file.0, &save).unwrap();
+        let loaded = load_save_game_from(&file.0).unwrap();
+
+        assert_eq!(to_ron(&loaded), to_ron(&save));
+    }
+
+    #[test]
+    fn missing_saves_are_reported() {
+        assert!(matches!(load_save_game_from(&TempSave::new("missing").0), Err(SaveError::Missing)));
+    }
+
+    #[test]
+    fn outdated_saves_are_reported() {
+        let file = TempSave::write("outdated", "(version: 1, seed: 42)");
+
+        assert!(matches!(load_save_game_from(&file.0), Err(SaveError::Outdated { found: 1, expected: SAVE_VERSION })));
+    }
+
+    #[test]
+    fn corrupt_saves_are_reported() {
+        let no_header = TempSave::write("no_header", "not a save");
+        assert!(matches!(load_save_game_from(&no_header.0), Err(SaveError::Corrupt(_))));
+
+        let truncated = to_ron(&save());
+        let truncated = TempSave::write("truncated", &truncated[..truncated.len() / 2]);
+        assert!(matches!(load_save_game_from(&truncated.0), Err(SaveError::Corrupt(_))));
+    }
+
+    #[test]
+    fn invalid_durations_are_reported_as_corrupt() {
+        let corruptions: [(&str, fn(&mut SaveGame)); 4] = [
+            ("negative_build", |save| save.phase = SavedWavePhase::Build { remaining: -1. }),
+            ("nan_spacing", |save| if let SavedWavePhase::Spawning { groups } = &mut save.phase { groups[0].spacing = f32::NAN; }),
+            ("negative_elapsed", |save| save.enemies[0].status_effects[0].elapsed = -0.5),
+            ("infinite_on_hit", |save| save.projectiles[0].on_hit_effect.as_mut().unwrap().duration = f32::INFINITY)
+        ];
+
+        for (name, corrupt) in corruptions {
+            let mut save = save();
+            corrupt(&mut save);
+
+            let file = TempSave::write(name, &to_ron(&save));
+            assert!(matches!(load_save_game_from(&file.0), Err(SaveError::Corrupt(err)) if err.contains("duration")), "{name} should be rejected");
+        }
+    }
+}
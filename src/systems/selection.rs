@@ -0,0 +1,8 @@
+use crate::components::turrets::*;
+
+/// Live range of a turret, including upgrades and aura bonuses.
+pub fn turret_range(targeting: Option<&TargetingTurret>, aoe: Option<&AoETurret>, aura: Option<&BuffAura>) -> Option<f32> {
+    aoe.map(|aoe| aoe.range)
+        .or(aura.map(|aura| aura.radius))
+        .or(targeting.and_then(|targeting| targeting.targeting_radius))
+}
@@ -0,0 +1,110 @@
+use bevy::{asset::{LoadState, UntypedAssetId}, prelude::*};
+
+use crate::{
+    components::{build::BuildGrid, economy::*, enemies::*, paths::*, session::*, turrets::CurrentTurretDefinitions, upgrades::CurrentUpgradeTrees, waves::*},
+    states::GameState
+};
+
+pub fn reset_session_system(
+    mut commands: Commands,
+    mut spawner: ResMut<WaveSpawner>,
+    mut grid: ResMut<BuildGrid>,
+    mut stats: ResMut<SessionStats>
+) {
+    *spawner = WaveSpawner::default();
+    grid.occupied.clear();
+    *stats = SessionStats::default();
+    commands.insert_resource(Lives::default());
+}
+
+pub fn loading_system(
+    asset_server: Res<AssetServer>,
+    current_map: Res<CurrentMap>,
+    current_waves: Res<CurrentWaves>,
+    current_enemies: Res<CurrentEnemyDefinitions>,
+    current_turrets: Res<CurrentTurretDefinitions>,
+    current_upgrades: Res<CurrentUpgradeTrees>,
+    paths: Res<MapPaths>,
+    mut failed: EventWriter<LoadingFailed>,
+    mut next_state: ResMut<NextState<GameState>>
+) {
+    let ids: [UntypedAssetId; 5] = [
+        current_map.0.id().untyped(),
+        current_waves.0.id().untyped(),
+        current_enemies.0.id().untyped(),
+        current_turrets.0.id().untyped(),
+        current_upgrades.0.id().untyped()
+    ];
+
+    for id in ids {
+        if let Some(LoadState::Failed(err)) = asset_server.get_load_state(id) {
+            let path = asset_server.get_path(id).map_or_else(|| "<unknown>".into(), |path| path.to_string());
+            error!("Could not load {path}: {err}");
+            failed.send(LoadingFailed { path, error: err.to_string() });
+            next_state.set(GameState::MainMenu);
+            return;
+        }
+    }
+
+    let loaded = ids.into_iter().all(|id| asset_server.is_loaded_with_dependencies(id));
+
+    if loaded && !paths.lanes.is_empty() {
+        next_state.set(GameState::InGame);
+    }
+}
+
+pub fn lose_lives_system(
+    mut events: EventReader<EnemyLeaked>,
+    mut lives: ResMut<Lives>,
+    current_definitions: Res<CurrentEnemyDefinitions>,
+    definitions: Res<Assets<EnemyDefinitions>>,
+    mut next_state: ResMut<NextState<GameState>>
+) {
+    let definitions = definitions.get(&current_definitions.0);
+
+    for event in events.read() {
+        let life_cost = definitions
+            .and_then(|definitions| definitions.get(event.enemy_type))
+            .map_or(1, |definition| definition.life_cost);
+
+        lives.0 = lives.0.saturating_sub(life_cost);
+        info!("{} leaked, {} lives left", event.enemy_type, lives.0);
+    }
+
+    if lives.0 == 0 {
+        next_state.set(GameState::GameOver);
+    }
+}
+
+pub fn victory_system(
+    spawner: Res<WaveSpawner>,
+    mut next_state: ResMut<NextState<GameState>>
+) {
+    if matches!(spawner.phase, WavePhase::Finished) {
+        next_state.set(GameState::Victory);
+    }
+}
+
+pub fn session_stats_system(
+    mut stats: ResMut<SessionStats>,
+    mut killed: EventReader<EnemyKilled>,
+    mut leaked: EventReader<EnemyLeaked>,
+    mut cleared: EventReader<WaveCleared>,
+    mut transactions: EventReader<Transaction>
+) {
+    stats.enemies_killed += killed.read().count() as u32;
+    stats.enemies_leaked += leaked.read().count() as u32;
+    stats.waves_cleared += cleared.read().count();
+
+    for transaction in transactions.read() {
+        if transaction.amount >= 0 {
+            stats.money_earned += transaction.amount as u64;
+        } else {
+            stats.money_spent += transaction.amount.unsigned_abs();
+        }
+
+        if matches!(transaction.kind, TransactionKind::Build(_)) {
+            stats.turrets_built += 1;
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use bevy::prelude::*;
+use bevy_prng::ChaCha8Rng;
+use bevy_rand::prelude::GlobalEntropy;
+use rand_core::SeedableRng;
+
+use crate::components::{build::BuildGrid, economy::SellTurret, simulation::*, turrets::*, upgrades::UpgradeTurret, waves::CallNextWave};
+
+pub fn seed_rng(rng: &mut GlobalEntropy<ChaCha8Rng>, seed: u64) {
+    *rng = GlobalEntropy::<ChaCha8Rng>::seed_from_u64(seed);
+}
+
+pub fn seed_session_system(
+    mut commands: Commands,
+    requested: Option<Res<RequestedSeed>>,
+    mut rng: ResMut<GlobalEntropy<ChaCha8Rng>>
+) {
+    let seed = match requested {
+        Some(requested) => {
+            commands.remove_resource::<RequestedSeed>();
+            requested.0
+        },
+        None => rand::random()
+    };
+
+    seed_rng(&mut rng, seed);
+    commands.insert_resource(SessionSeed(seed));
+    commands.insert_resource(SimulationTick::default());
+    commands.insert_resource(SpawnCounter::default());
+    info!("Session seed: {seed}");
+}
+
+pub fn tick_simulation_system(mut tick: ResMut<SimulationTick>) {
+    tick.0 += 1;
+}
+
+/// Turns commands into the gameplay events they stand for, building is handled by `place_turret_system`
+/// and the game speed by `game_speed_system`.
+pub fn dispatch_player_commands_system(
+    mut events: EventReader<PlayerCommand>,
+    grid: Res<BuildGrid>,
+    mut targeting: Query<&mut TargetingTurret>,
+    mut sell_events: EventWriter<SellTurret>,
+    mut upgrade_events: EventWriter<UpgradeTurret>,
+    mut call_events: EventWriter<CallNextWave>
+) {
+    for event in events.read() {
+        match event {
+            PlayerCommand::Build { .. } => (),
+            PlayerCommand::Sell { cell } => {
+                if let Some(turret) = grid.occupied.get(cell) {
+                    sell_events.send(SellTurret(*turret));
+                }
+            },
+            PlayerCommand::Upgrade { cell, branch } => {
+                if let Some(turret) = grid.occupied.get(cell) {
+                    upgrade_events.send(UpgradeTurret { turret: *turret, branch: *branch });
+                }
+            },
+            PlayerCommand::CycleTargeting { cell } => {
+                let Some(mut turret) = grid.occupied.get(cell).and_then(|turret| targeting.get_mut(*turret).ok()) else {
+                    continue;
+                };
+
+                turret.priority = turret.priority.next();
+                info!("Targeting priority: {}", turret.priority);
+            },
+            PlayerCommand::CallNextWave => {
+                call_events.send(CallNextWave);
+            },
+            PlayerCommand::SetGameSpeed { .. } => ()
+        }
+    }
+}
+
+pub fn game_speed_system(
+    mut events: EventReader<PlayerCommand>,
+    mut time: ResMut<Time<Virtual>>
+) {
+    for event in events.read() {
+        if let PlayerCommand::SetGameSpeed { speed } = event {
+            time.set_relative_speed(*speed);
+            info!("Game speed: {speed}x");
+        }
+    }
+}
+
+pub fn reset_game_speed_system(mut time: ResMut<Time<Virtual>>) {
+    time.set_relative_speed(1.);
+}
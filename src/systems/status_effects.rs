@@ -0,0 +1,48 @@
+use bevy::prelude::*;
+
+use crate::components::{damage::*, enemies::Enemy, simulation::SpawnId, status_effects::*};
+
+pub fn apply_status_effects_system(
+    mut events: EventReader<ApplyStatusEffect>,
+    mut enemies: Query<&mut StatusEffects, With<Enemy>>,
+    spawn_ids: Query<&SpawnId>
+) {
+    // Stacks and refreshed sources depend on the order effects land in, see `apply_damage_system`
+    let spawn_id = |entity: Option<Entity>| entity.and_then(|entity| spawn_ids.get(entity).ok().copied());
+    let mut events: Vec<_> = events.read().collect();
+    events.sort_by_key(|event| (spawn_id(Some(event.target)), spawn_id(event.source)));
+
+    for event in events {
+        if let Ok(mut status_effects) = enemies.get_mut(event.target) {
+            status_effects.apply(&event.effect, event.source);
+        }
+    }
+}
+
+pub fn status_effect_tick_system(
+    time: Res<Time>,
+    mut enemies: Query<(Entity, &mut StatusEffects)>,
+    mut damage_events: EventWriter<DamageEvent>
+) {
+    for (entity, mut status_effects) in &mut enemies {
+        if status_effects.active.is_empty() {
+            continue;
+        }
+
+        for active in &mut status_effects.active {
+            active.timer.tick(time.delta());
+
+            if let StatusEffectKind::Burn { damage_per_second } = active.effect.kind {
+                damage_events.send(DamageEvent {
+                    source: active.source,
+                    target: entity,
+                    amount: damage_per_second * active.stacks as f32 * time.delta_seconds(),
+                    damage_type: DamageType::Fire
+                });
+            }
+        }
+
+        status_effects.active.retain(|active| !active.timer.finished());
+    }
+}
+
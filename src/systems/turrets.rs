@@ -1,55 +1,36 @@
 
-use bevy::{math::Vec3, prelude::*, sprite::{MaterialMesh2dBundle, Mesh2dHandle}, window::{PrimaryWindow, Window}};
+use bevy::{ecs::system::EntityCommands, prelude::*};
 use bevy_prng::ChaCha8Rng;
 use bevy_rand::prelude::GlobalEntropy;
 use rand_core::RngCore;
 
-use crate::{components::turrets::*, turret_bundles::PulseBlasterBundle, GameTextures, WinSize};
-
-pub fn move_target(
-    mut target_query: Query<(&mut Transform, &mut Target)>,
-    windows: Query<&Window, With<PrimaryWindow>>
-) {
-    let (mut transform, mut target) = target_query.single_mut();
-    let window = windows.single();
-
-    if let Some(pos) = window.cursor_position() {
-        let pos = window_to_world_coords(pos, window.size());
-
-        target.pos = pos;
-        transform.translation.x = pos.x;
-        transform.translation.y = pos.y; 
-    }
-}
-
-pub fn window_to_world_coords(cursor_pos: Vec2, window_size: Vec2) -> Vec3 {
-    Vec3 { 
-        x: cursor_pos.x - window_size.x / 2.0,
-        y: window_size.y / 2.0 - cursor_pos.y,
-        z: 0.0
-    }
-}
+use crate::{components::{damage::*, enemies::*, paths::{ArenaBounds, MapPaths, PathFollower}, simulation::{SpawnCounter, SpawnId}, spatial::*, status_effects::*, turrets::*, upgrades::ReplayUpgrades}, states::InSession, turret_bundles::TurretBundle, TurretType};
 
 const PROJECTILE_SPEED: f32 = 300.0;
-const TARGET_RADIUS: f32 = 20.0;
+const DEFAULT_PROJECTILE_DAMAGE: f32 = 1.0;
 const DESPAWN_MARGIN: f32 = 200.0;
 
 pub fn projectile_system(
     mut commands: Commands,
     time: Res<Time>,
-    win_size: Res<WinSize>,
-    mut projectiles: Query<(Entity, &mut Transform, &Projectile, Option<&LinearVelocity>, Option<&Explosive>), Without<Target>>,
-    targets: Query<&Target>,
+    bounds: Res<ArenaBounds>,
+    mut projectiles: Query<(Entity, &mut Transform, &Projectile, Option<&LinearVelocity>, Option<&Explosive>, Option<&InstantDamage>, Option<&FiredBy>, Option<&DamageKind>, Option<&OnHitEffect>), Without<Enemy>>,
+    grid: Res<EnemyGrid>,
+    mut damage_events: EventWriter<DamageEvent>,
+    mut effect_events: EventWriter<ApplyStatusEffect>
 ) {
     let delta = time.delta_seconds();
-    let target = targets.single();
 
     for (
         entity,
         mut transform,
         projectile,
         velocity,
-        explosive
+        explosive,
+        damage,
+        fired_by,
+        damage_kind,
+        on_hit_effect
     ) in &mut projectiles {
         let speed = velocity.map_or(PROJECTILE_SPEED, |v| v.0);
         let current_angle = transform.rotation.to_euler(EulerRot::XYZ).2 + std::f32::consts::FRAC_PI_2;
@@ -57,62 +38,59 @@ pub fn projectile_system(
         let velocity_vec = Vec3::new(current_angle.cos() * speed, current_angle.sin() * speed, 0.0);
         transform.translation += velocity_vec * delta;
 
-        let distance = transform.translation.distance(target.pos);
+        let source = fired_by.map(|fired_by| fired_by.0);
+        let damage_type = damage_kind.map_or(DamageType::default(), |kind| kind.0);
+        let hit = grid.overlapping(transform.translation.truncate(), projectile.radius).next();
 
-        if distance < projectile.radius + TARGET_RADIUS {
+        if let Some(&GridEnemy { entity: target, .. }) = hit {
             commands.entity(entity).despawn();
+            if let Some(damage) = damage {
+                damage_events.send(DamageEvent {
+                    source,
+                    target,
+                    amount: damage.0,
+                    damage_type
+                });
+            }
+
+            if let Some(on_hit_effect) = on_hit_effect {
+                effect_events.send(ApplyStatusEffect {
+                    target,
+                    effect: on_hit_effect.0.clone(),
+                    source
+                });
+            }
+
             if let Some(explosive) = explosive {
-                commands.spawn(
+                commands.spawn((
+                    StateScoped(InSession),
                     ExplosionToSpawn {
                         damage: explosive.damage,
                         radius: explosive.radius,
-                        pos: transform.translation
+                        pos: transform.translation,
+                        source,
+                        damage_type
                     }
-                );
+                ));
             }
 
             continue;
         }
 
-        if projectile.auto_despawn {
-            if transform.translation.y > win_size.height / 2.0 + DESPAWN_MARGIN
-                || transform.translation.y < -win_size.height / 2.0 - DESPAWN_MARGIN
-                || transform.translation.x > win_size.width / 2.0 + DESPAWN_MARGIN
-                || transform.translation.x < -win_size.width / 2.0 - DESPAWN_MARGIN
-            {
-                commands.entity(entity).despawn();
-            }
+        if projectile.auto_despawn && !bounds.0.inflate(DESPAWN_MARGIN).contains(transform.translation.truncate()) {
+            commands.entity(entity).despawn();
         }
     }
 }
 
 pub fn decaying_projectile_system(
     mut commands: Commands,
-    mut materials: ResMut<Assets<ColorMaterial>>,
     time: Res<Time>,
-    mut projectiles: Query<(Entity, &mut Decaying, AnyOf<(&mut Sprite, &Handle<ColorMaterial>)>), With<Projectile>>
+    mut projectiles: Query<(Entity, &mut Decaying)>
 ) {
-    for (
-        entity,
-        mut decaying,
-        sprite_or_material
-    ) in &mut projectiles {
+    for (entity, mut decaying) in &mut projectiles {
         decaying.decay_timer.tick(time.delta());
 
-        if decaying.decay_type == DecayType::Transparency {
-            let alpha = decaying.decay_timer.fraction_remaining();
-            
-            match sprite_or_material {
-                (Some(mut sprite), None) => sprite.color.set_alpha(alpha),
-                (None, Some(handle)) => {
-                    if let Some(material) = materials.get_mut(handle) {
-                        material.color.set_alpha(alpha)
-                    }
-                },
-                _ => ()
-            }
-        }
-
         if decaying.decay_timer.finished() {
             commands.entity(entity).despawn();
         }
@@ -121,16 +99,18 @@ pub fn decaying_projectile_system(
 
 pub fn homing_projectile_system(
     time: Res<Time>,
-    mut projectiles: Query<(&Homing, &mut Transform), With<Projectile>>,
-    targets: Query<&Target>
+    mut projectiles: Query<(&Homing, &mut Transform), (With<Projectile>, Without<Enemy>)>,
+    grid: Res<EnemyGrid>
 ) {
-    let target = targets.single();
-
     for (homing, mut transform) in &mut projectiles {
-        let distance = (target.pos - transform.translation).truncate();
-        if distance.length() > homing.homing_distance {
+        let position = transform.translation.truncate();
+        let closest = grid.within(position, homing.homing_distance)
+            .map(|enemy| enemy.pos - position)
+            .min_by(|a, b| a.length_squared().total_cmp(&b.length_squared()));
+
+        let Some(distance) = closest else {
             continue;
-        }
+        };
 
         let target_angle = distance.y.atan2(distance.x) - std::f32::consts::FRAC_PI_2;
         let current_angle = transform.rotation.to_euler(EulerRot::XYZ).2;
@@ -150,13 +130,23 @@ pub fn homing_projectile_system(
 
 pub fn explosion_spawn_system(
     mut commands: Commands,
-    mut materials: ResMut<Assets<ColorMaterial>>,
-    mut meshes: ResMut<Assets<Mesh>>,
 	query: Query<(Entity, &ExplosionToSpawn)>,
+    grid: Res<EnemyGrid>,
+    mut damage_events: EventWriter<DamageEvent>
 ) {
     for (entity, explosion_to_spawn) in &query {
+        for enemy in grid.overlapping(explosion_to_spawn.pos.truncate(), explosion_to_spawn.radius) {
+            damage_events.send(DamageEvent {
+                source: explosion_to_spawn.source,
+                target: enemy.entity,
+                amount: explosion_to_spawn.damage,
+                damage_type: explosion_to_spawn.damage_type
+            });
+        }
+
 		commands
 			.spawn((
+                StateScoped(InSession),
                 Explosion {
                     radius: explosion_to_spawn.radius,
                     pos: explosion_to_spawn.pos
@@ -172,15 +162,7 @@ pub fn explosion_spawn_system(
                         animate_alpha: true
                     })
                 },
-                MaterialMesh2dBundle {
-                    mesh: Mesh2dHandle(meshes.add(Circle {radius: explosion_to_spawn.radius })),
-                    material: materials.add(Color::srgb(1.0, 1.0, 0.0)),
-                    transform: Transform {
-                        translation: explosion_to_spawn.pos.with_z(100.),
-                        ..default()
-                    },
-                    ..default()
-                }
+                TransformBundle::from_transform(Transform::from_translation(explosion_to_spawn.pos.with_z(100.)))
 			));
 
 		commands.entity(entity).despawn();
@@ -191,51 +173,10 @@ pub fn explosion_spawn_system(
 pub fn aoe_animation_system(
 	mut commands: Commands,
 	time: Res<Time>,
-    mut materials: ResMut<Assets<ColorMaterial>>,
-	mut animations: Query<(Entity, &mut AoEAnimation, &mut Transform, &Handle<ColorMaterial>)>
+	mut animations: Query<(Entity, &mut AoEAnimation)>
 ) {
-    for (
-        entity,
-        mut animation,
-        mut animation_transform,
-        handle
-    ) in &mut animations {
+    for (entity, mut animation) in &mut animations {
         animation.timer.tick(time.delta());
-        let t = animation.timer.fraction();
-        let t_1 = animation.timer.fraction_remaining();
-
-        if let Some(radius_animation) = &animation.radius_animation {
-            match radius_animation {
-                RadiusAnimation::FromBaseRadius { grow_speed } => {
-                    let scale = animation_transform.scale.x + grow_speed * time.delta_seconds();
-                    animation_transform.scale = Vec3::splat(scale);
-                },
-                RadiusAnimation::FromStartToEnd { start_radius, end_radius } => {
-                    let radius = start_radius.lerp(*end_radius, t);
-                    animation_transform.scale = Vec3::splat(radius);
-                },
-            }
-        }
-
-        if let Some(color_animation) = &animation.color_animation {
-            if let Some(material) = materials.get_mut(handle) {
-                let start = color_animation.start_color.to_srgba();
-                let end = color_animation.end_color.to_srgba();
-
-                let color = Color::srgba(
-                    start.red * t_1 + end.red * t,
-                    start.green * t_1 + end.green * t,
-                    start.blue * t_1 + end.blue * t,
-                    if color_animation.animate_alpha {
-                        color_animation.alpha_factor.unwrap_or(1.0) * t_1
-                    } else {
-                        material.color.alpha()
-                    }
-                );
-
-                material.color = color;
-            }
-        }   
 
         if animation.despawn_on_end && animation.timer.finished() {
             commands.entity(entity).despawn();
@@ -268,14 +209,19 @@ const MAX_RANDOM_ROTATION_ANGLE: f32 = std::f32::consts::FRAC_PI_2;
 
 pub fn idle_rotation_system(
     time: Res<Time>,
-    mut query: Query<(&mut Transform, &mut IdleRotation, Option<&RotationSpeed>)>,
+    mut query: Query<(&mut Transform, &mut IdleRotation, Option<&RotationSpeed>, &SpawnId)>,
     mut rng: ResMut<GlobalEntropy<ChaCha8Rng>>
 ) {
+    // Turrets draw from the shared generator, so they take turns in spawn order
+    let mut turrets: Vec<_> = query.iter_mut().collect();
+    turrets.sort_unstable_by_key(|(.., spawn_id)| **spawn_id);
+
     for (
         mut transform,
         mut idle_rotation,
-        rotation_speed
-    ) in &mut query {
+        rotation_speed,
+        _
+    ) in turrets {
         if !idle_rotation.is_idle {
             continue;
         }
@@ -324,26 +270,33 @@ fn shortest_angle_diff(from: f32, to: f32) -> f32 {
 
 pub fn turret_targeting_system(
     time: Res<Time>,
-    mut turrets: Query<(&mut TargetingTurret, &mut Transform, Option<&RotationSpeed>)>,
-    targets: Query<&Target>
+    mut turrets: Query<(&mut TargetingTurret, &mut Transform, Option<&RotationSpeed>), Without<Enemy>>,
+    enemies: Query<(Entity, &SpawnId, &Transform, &Health, &MovementSpeed, &PathFollower, Has<Stealth>, Has<Revealed>), With<Enemy>>,
+    grid: Res<EnemyGrid>,
+    paths: Res<MapPaths>
 ) {
-    let target_position = targets.single().pos;
-
     for (
         mut turret,
         mut turret_transform,
         rotation_speed
     ) in &mut turrets {
-        let target_distance = (target_position - turret_transform.translation).truncate();
-        let is_inside_radius = match turret.targeting_radius {
-            Some(radius) => radius >= target_distance.length(),
-            None => true
+        let turret_position = turret_transform.translation.truncate();
+        let selected = match turret.targeting_radius {
+            Some(radius) => {
+                let in_range = enemies.iter_many(grid.within(turret_position, radius).map(|enemy| enemy.entity));
+                select_target(in_range, turret.priority, turret_position, &paths)
+            },
+            None => select_target(enemies.iter(), turret.priority, turret_position, &paths)
         };
 
-        turret.has_target = is_inside_radius;
-        if !is_inside_radius {
+        turret.target = selected.map(|(entity, _)| entity);
+        turret.has_target = selected.is_some();
+
+        let Some((_, target_position)) = selected else {
             continue;
-        }
+        };
+
+        let target_distance = (target_position - turret_transform.translation).truncate();
 
         let target_angle = target_distance.y.atan2(target_distance.x) - std::f32::consts::FRAC_PI_2;
         let current_angle = turret_transform.rotation.to_euler(EulerRot::XYZ).2;
@@ -367,22 +320,60 @@ pub fn turret_targeting_system(
 }
 
 
+type TargetCandidate<'a> = (Entity, &'a SpawnId, &'a Transform, &'a Health, &'a MovementSpeed, &'a PathFollower, bool, bool);
+
+/// Picks the visible enemy the priority favours, stealthed ones can only be targeted while revealed.
+fn select_target<'a>(
+    candidates: impl Iterator<Item = TargetCandidate<'a>>,
+    priority: TargetingPriority,
+    turret_position: Vec2,
+    paths: &MapPaths
+) -> Option<(Entity, Vec3)> {
+    // Lanes merge into each other with different lengths, so progress is measured towards the exit
+    let remaining = |follower: &PathFollower| paths.get(follower.lane).map_or(0., |lane| lane.length() - follower.distance);
+
+    let score = |(_, _, transform, health, speed, follower, ..): &TargetCandidate| {
+        match priority {
+            TargetingPriority::First => -remaining(follower),
+            TargetingPriority::Last => remaining(follower),
+            TargetingPriority::Strongest => health.current,
+            TargetingPriority::Weakest => -health.current,
+            TargetingPriority::Closest => -turret_position.distance_squared(transform.translation.truncate()),
+            TargetingPriority::Fastest => speed.0,
+        }
+    };
+
+    // Ties go to the enemy spawned first, whatever order the candidates come in
+    candidates
+        .filter(|(.., stealth, revealed)| !stealth || *revealed)
+        .max_by(|a, b| score(a).total_cmp(&score(b)).then_with(|| b.1.cmp(a.1)))
+        .map(|(entity, _, transform, ..)| (entity, transform.translation))
+}
+
 pub fn projectile_turret_attack_system(
     mut commands: Commands,
-    game_textures: Res<GameTextures>,
     time: Res<Time>,
-    mut materials: ResMut<Assets<ColorMaterial>>,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut turrets: Query<(&TargetingTurret, &Transform, Option<&mut AttackDelay>, Option<&SpawnOffset>, Option<&AttackDispersion>), With<ProjectileTurret>>,
+    mut turrets: Query<(Entity, &TargetingTurret, &Transform, Option<&mut AttackDelay>, Option<&SpawnOffset>, Option<&AttackDispersion>, Option<&DamageKind>, Option<&OnHitEffect>, Option<&InstantDamage>, Option<&LinearVelocity>, Option<&Homing>, Option<&Explosive>, &SpawnId), With<ProjectileTurret>>,
     mut rng: ResMut<GlobalEntropy<ChaCha8Rng>>
 ) {
+    let mut turrets: Vec<_> = turrets.iter_mut().collect();
+    turrets.sort_unstable_by_key(|(.., spawn_id)| **spawn_id);
+
     for (
+        turret_entity,
         turret,
         turret_transform,
         attack_delay,
         spawn_offset,
-        attack_dispersion
-    ) in &mut turrets {
+        attack_dispersion,
+        damage_kind,
+        on_hit_effect,
+        projectile_damage,
+        projectile_velocity,
+        homing,
+        explosive,
+        _
+    ) in turrets {
         if !turret.has_target {
             continue;
         }
@@ -406,7 +397,8 @@ pub fn projectile_turret_attack_system(
         let offset = turret_transform.rotation * spawn_offset.map_or(Vec3::ZERO, |off| off.0);
         let spawn_translation = turret_transform.translation + offset;
 
-        commands.spawn((
+        let mut projectile = commands.spawn((
+            StateScoped(InSession),
             Projectile {
                 auto_despawn: true,
                 radius: 1.
@@ -435,28 +427,28 @@ pub fn projectile_turret_attack_system(
             //     damage: 5.,
             //     radius: 20.
             // },
-            InstantDamage(1.),
-            LinearVelocity(200.),
-            // MaterialMesh2dBundle {
-            //     mesh: Mesh2dHandle(meshes.add(Circle { radius: 2. })),
-            //     material: materials.add(Color::srgb(0.64, 0.12, 0.36)),
-            //     transform: Transform {
-            //         translation: spawn_translation.with_z(99.),
-            //         rotation: direction,
-            //         ..default()
-            //     },
-            //     ..default()
-            // }
-            SpriteBundle {
-                texture: game_textures.bullet.clone(),
-                transform: Transform {
-                    translation: spawn_translation,
-                    rotation: direction,
-                    ..default()
-                },
+            InstantDamage(projectile_damage.map_or(DEFAULT_PROJECTILE_DAMAGE, |damage| damage.0)),
+            LinearVelocity(projectile_velocity.map_or(PROJECTILE_SPEED, |velocity| velocity.0)),
+            FiredBy(turret_entity),
+            DamageKind(damage_kind.map_or(DamageType::default(), |kind| kind.0)),
+            TransformBundle::from_transform(Transform {
+                translation: spawn_translation,
+                rotation: direction,
                 ..default()
-            }
+            })
         ));
+
+        if let Some(on_hit_effect) = on_hit_effect {
+            projectile.insert(OnHitEffect(on_hit_effect.0.clone()));
+        }
+
+        if let Some(homing) = homing {
+            projectile.insert(homing.clone());
+        }
+
+        if let Some(explosive) = explosive {
+            projectile.insert(explosive.clone());
+        }
     }
 }
 
@@ -468,13 +460,10 @@ fn map_u32_to_range(value: u32, min: f32, max: f32) -> f32 {
 pub fn aoe_turret_attack_system(
     time: Res<Time>,
     mut commands: Commands,
-    mut turrets: Query<(&AoETurret, &Transform, Option<&mut AttackDelay>), With<Turret>>,
-    mut materials: ResMut<Assets<ColorMaterial>>,
-    mut meshes: ResMut<Assets<Mesh>>,
-    targets: Query<&Target>
+    mut turrets: Query<(Entity, &AoETurret, &Transform, Option<&mut AttackDelay>, Option<&InstantDamage>, Option<&DamageKind>, Option<&OnHitEffect>), With<Turret>>,
+    grid: Res<EnemyGrid>
 ) {
-    let target = targets.single();
-    for (turret, transform, attack_delay) in &mut turrets {
+    for (turret_entity, turret, transform, attack_delay, damage, damage_kind, on_hit_effect) in &mut turrets {
         if let Some(mut attack_delay) = attack_delay {
             attack_delay.0.tick(time.delta());
             if !attack_delay.0.finished() {
@@ -482,17 +471,19 @@ pub fn aoe_turret_attack_system(
             }
         }
         
-        if !turret.always_attacking {
-            let target_distance = (target.pos - transform.translation).truncate();
-            if turret.range <= target_distance.length() {
-                continue;
-            }
+        if !turret.always_attacking && grid.overlapping(transform.translation.truncate(), turret.range).next().is_none() {
+            continue;
         }
 
         commands.spawn((
+            StateScoped(InSession),
             AoEAttack {
                 pos: transform.translation,
-                radius: turret.range
+                radius: turret.range,
+                damage: damage.map_or(0., |damage| damage.0),
+                damage_type: damage_kind.map_or(DamageType::default(), |kind| kind.0),
+                effect: on_hit_effect.map(|on_hit_effect| on_hit_effect.0.clone()),
+                source: Some(turret_entity)
             },
             AoEAnimation {
                 timer: Timer::from_seconds(0.5, TimerMode::Once),
@@ -505,139 +496,538 @@ pub fn aoe_turret_attack_system(
                     animate_alpha: true
                 })
             },
-            MaterialMesh2dBundle {
-                mesh: Mesh2dHandle(meshes.add(Annulus {
-                    inner_circle: Circle { radius: 0.95 },
-                    outer_circle: Circle { radius: 1. }
-                })),
-                material: materials.add(Color::srgb(0.64, 0.12, 0.36)),
-                transform: Transform {
-                    translation: transform.translation.with_z(99.),
-                    ..default()
-                },
-                ..default()
+            TransformBundle::from_transform(Transform::from_translation(transform.translation.with_z(99.)))
+        ));
+    }
+}
+
+pub fn aoe_attack_damage_system(
+    attacks: Query<&AoEAttack, Added<AoEAttack>>,
+    grid: Res<EnemyGrid>,
+    mut damage_events: EventWriter<DamageEvent>,
+    mut effect_events: EventWriter<ApplyStatusEffect>
+) {
+    for attack in &attacks {
+        for &GridEnemy { entity: target, .. } in grid.overlapping(attack.pos.truncate(), attack.radius) {
+            if attack.damage > 0. {
+                damage_events.send(DamageEvent {
+                    source: attack.source,
+                    target,
+                    amount: attack.damage,
+                    damage_type: attack.damage_type
+                });
             }
+
+            if let Some(effect) = &attack.effect {
+                effect_events.send(ApplyStatusEffect {
+                    target,
+                    effect: effect.clone(),
+                    source: attack.source
+                });
+            }
+        }
+    }
+}
+
+pub fn continuous_damage_system(
+    time: Res<Time>,
+    query: Query<(&ContinousDamage, &DamageTarget, Option<&FiredBy>, Option<&DamageKind>)>,
+    mut damage_events: EventWriter<DamageEvent>
+) {
+    for (damage, target, fired_by, damage_kind) in &query {
+        damage_events.send(DamageEvent {
+            source: fired_by.map(|fired_by| fired_by.0),
+            target: target.0,
+            amount: damage.damage_per_second * time.delta_seconds(),
+            damage_type: damage_kind.map_or(DamageType::Energy, |kind| kind.0)
+        });
+    }
+}
+
+pub fn spawn_turret(
+    commands: &mut Commands,
+    spawn_ids: &mut SpawnCounter,
+    definitions: &TurretDefinitions,
+    turret_type: &TurretType,
+    pos: Vec3,
+    priority: TargetingPriority
+) -> Option<Entity> {
+    let definition = definitions.get(turret_type)?;
+    let mut turret = commands.spawn((StateScoped(InSession), spawn_ids.assign(), TurretBundle::new(turret_type.clone(), definition, pos)));
+    insert_definition_components(&mut turret, definition, priority);
+
+    Some(turret.id())
+}
+
+/// Inserts the components making up the turret's attack, replacing the ones from a previous definition.
+fn insert_definition_components(turret: &mut EntityCommands, definition: &TurretDefinition, priority: TargetingPriority) {
+    turret.remove::<(
+        (ProjectileTurret, LaserTurret, RailGunTurret, ChainLightningTurret, AoETurret, BuffAura),
+        (InstantDamage, LinearVelocity, ContinousDamage, Homing, Explosive, OnHitEffect),
+        (TargetingTurret, IdleRotation, RotationSpeed, AttackDelay, AttackDispersion, SpawnOffset)
+    )>();
+
+    turret.insert((DamageKind(definition.damage_type), definition.base_stats()));
+
+    if definition.attack.is_targeted() {
+        turret.insert((
+            TargetingTurret {
+                targeting_radius: definition.targeting_radius,
+                rotation: 0.,
+                has_target: false,
+                target: None,
+                priority
+            },
+            IdleRotation::default()
         ));
     }
+
+    if let Some(rotation_speed) = definition.rotation_speed {
+        turret.insert(RotationSpeed(rotation_speed));
+    }
+
+    if let Some(attack_delay) = definition.attack_delay {
+        turret.insert(AttackDelay(Timer::from_seconds(attack_delay, TimerMode::Repeating)));
+    }
+
+    if let Some(dispersion) = definition.dispersion {
+        turret.insert(AttackDispersion(dispersion));
+    }
+
+    if let Some(spawn_offset) = definition.spawn_offset {
+        turret.insert(SpawnOffset(spawn_offset.extend(0.)));
+    }
+
+    if let Some(homing) = &definition.homing {
+        turret.insert(homing.clone());
+    }
+
+    if let Some(explosive) = &definition.explosive {
+        turret.insert(explosive.clone());
+    }
+
+    if let Some(effect) = &definition.on_hit_effect {
+        turret.insert(OnHitEffect(effect.clone()));
+    }
+
+    match definition.attack {
+        TurretAttack::Projectile { damage, speed } => {
+            turret.insert((ProjectileTurret, InstantDamage(damage), LinearVelocity(speed)));
+        },
+        TurretAttack::Laser { damage_per_second } => {
+            turret.insert((LaserTurret { laser_beam: None }, ContinousDamage { damage_per_second }));
+        },
+        TurretAttack::RailGun { charge_time, max_range, damage, falloff, beam_width } => {
+            turret.insert(RailGunTurret {
+                charge: Timer::from_seconds(charge_time, TimerMode::Once),
+                max_range,
+                damage,
+                falloff,
+                beam_width
+            });
+        },
+        TurretAttack::ChainLightning { damage, jumps, jump_range, falloff } => {
+            turret.insert((ChainLightningTurret { jumps, jump_range, falloff }, InstantDamage(damage)));
+        },
+        TurretAttack::AoE { range, damage, always_attacking } => {
+            turret.insert((AoETurret { range, always_attacking }, InstantDamage(damage)));
+        },
+        TurretAttack::Aura { radius, fire_rate_bonus, range_bonus, rotation_speed_bonus } => {
+            turret.insert(BuffAura { radius, fire_rate_bonus, range_bonus, rotation_speed_bonus, detection_bonus: 0. });
+        }
+    }
 }
 
-pub fn spawn_projectile_turret(
+/// Applies a changed definitions file to the turrets already on the map.
+pub fn reload_turret_definitions_system(
     mut commands: Commands,
-    game_textures: Res<GameTextures>,
-    windows: Query<&Window, With<PrimaryWindow>>
+    mut events: EventReader<AssetEvent<TurretDefinitions>>,
+    current_definitions: Res<CurrentTurretDefinitions>,
+    definitions: Res<Assets<TurretDefinitions>>,
+    turrets: Query<(Entity, &Turret, Option<&TargetingTurret>)>
 ) {
-    let window = windows.single();
-    let Some(cursor_pos) = window.cursor_position() else {
+    let modified = events.read().any(|event| event.is_modified(&current_definitions.0));
+    if !modified {
+        return;
+    }
+
+    let Some(definitions) = definitions.get(&current_definitions.0) else {
         return;
     };
 
-    let pos = window_to_world_coords(cursor_pos, window.size());
+    info!("Turret definitions changed, updating {} turrets", turrets.iter().len());
 
-    commands.spawn(PulseBlasterBundle {
-        sprite: SpriteBundle {
-            texture: game_textures.arrow.clone(),
-            transform: Transform {
-                translation: pos,
-                ..default()
+    for (entity, turret, targeting) in &turrets {
+        let Some(definition) = definitions.get(&turret.0) else {
+            continue;
+        };
+
+        let priority = targeting.map_or(TargetingPriority::default(), |targeting| targeting.priority);
+        let mut turret = commands.entity(entity);
+
+        insert_definition_components(&mut turret, definition, priority);
+        turret.insert(ReplayUpgrades);
+    }
+}
+
+const LASER_SPRITE_LENGTH: f32 = 12.0;
+const LASER_AIM_TOLERANCE: f32 = ROTATION_EPSILON * 2.0;
+const LASER_Z: f32 = 98.;
+
+pub fn laser_turret_attack_system(
+    mut commands: Commands,
+    mut laser_turrets: Query<(Entity, &mut LaserTurret, &TargetingTurret, &Transform, &ContinousDamage, Option<&SpawnOffset>, Option<&DamageKind>), Without<LaserBeam>>,
+    mut laser_beams: Query<(&mut Transform, &mut DamageTarget), With<LaserBeam>>,
+    enemies: Query<(&Transform, &Health), (With<Enemy>, Without<LaserBeam>, Without<LaserTurret>)>
+) {
+    for (
+        turret_entity,
+        mut laser_turret,
+        turret,
+        turret_transform,
+        damage,
+        spawn_offset,
+        damage_kind
+    ) in &mut laser_turrets {
+        let target = turret.target
+            .and_then(|target| enemies.get(target).ok().map(|(transform, health)| (target, transform, health)))
+            .filter(|(_, _, health)| !health.is_dead());
+
+        let Some((target, target_transform, _)) = target else {
+            remove_laser_beam(&mut commands, &mut laser_turret);
+            continue;
+        };
+
+        let target_position = target_transform.translation.truncate();
+        let beam_start = (turret_transform.translation + turret_transform.rotation * spawn_offset.map_or(Vec3::ZERO, |off| off.0)).truncate();
+        let to_target = target_position - beam_start;
+
+        let target_angle = to_target.y.atan2(to_target.x) - std::f32::consts::FRAC_PI_2;
+        let current_angle = turret_transform.rotation.to_euler(EulerRot::XYZ).2;
+
+        if shortest_angle_diff(current_angle, target_angle).abs() > LASER_AIM_TOLERANCE {
+            remove_laser_beam(&mut commands, &mut laser_turret);
+            continue;
+        }
+
+        let distance = to_target.length();
+        let beam_transform = Transform {
+            translation: (beam_start + to_target / 2.0).extend(LASER_Z),
+            rotation: Quat::from_rotation_z(target_angle),
+            scale: Vec3::new(1.0, distance / LASER_SPRITE_LENGTH, 1.0)
+        };
+
+        if let Some((mut laser_transform, mut damage_target)) = laser_turret.laser_beam.and_then(|beam| laser_beams.get_mut(beam).ok()) {
+            *laser_transform = beam_transform;
+            damage_target.0 = target;
+            continue;
+        }
+
+        let beam = commands.spawn((
+            StateScoped(InSession),
+            LaserBeam,
+            ContinousDamage {
+                damage_per_second: damage.damage_per_second
             },
-            ..default()
-        },
-        ..default()
-    });
+            DamageTarget(target),
+            FiredBy(turret_entity),
+            DamageKind(damage_kind.map_or(DamageType::Energy, |kind| kind.0)),
+            TransformBundle::from_transform(beam_transform)
+        )).id();
+
+        laser_turret.laser_beam = Some(beam);
+    }
+}
+
+fn remove_laser_beam(commands: &mut Commands, laser_turret: &mut LaserTurret) {
+    if let Some(beam) = laser_turret.laser_beam.take() {
+        if let Some(mut beam) = commands.get_entity(beam) {
+            beam.despawn();
+        }
+    }
 }
 
-pub fn spawn_aoe_turret(
+pub fn orphaned_laser_beam_system(
     mut commands: Commands,
-    game_textures: Res<GameTextures>,
-    windows: Query<&Window, With<PrimaryWindow>>
+    laser_beams: Query<(Entity, &FiredBy), With<LaserBeam>>,
+    laser_turrets: Query<&LaserTurret>
 ) {
-    let window = windows.single();
-    let Some(cursor_pos) = window.cursor_position() else {
-        return;
-    };
+    for (beam, fired_by) in &laser_beams {
+        let owned = laser_turrets.get(fired_by.0)
+            .is_ok_and(|laser_turret| laser_turret.laser_beam == Some(beam));
+
+        if !owned {
+            commands.entity(beam).despawn();
+        }
+    }
+}
 
-    let pos = window_to_world_coords(cursor_pos, window.size());
+const RAIL_GUN_BEAM_SPRITE_SIZE: f32 = 20.0;
+const RAIL_GUN_BEAM_DECAY: f32 = 0.6;
+const RAIL_GUN_AIM_TOLERANCE: f32 = ROTATION_EPSILON * 2.0;
 
-    commands.spawn((
-        Turret(crate::TurretType::CryoGenerator),
-        AoETurret {
-            range: 100.,
-            always_attacking: false
-        },
-        AttackDelay(Timer::from_seconds(1., TimerMode::Repeating)),
-        SpriteBundle {
-            texture: game_textures.arrow.clone(),
-            transform: Transform {
-                translation: pos,
-                ..default()
+pub fn rail_gun_attack_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut rail_guns: Query<(Entity, &mut RailGunTurret, &TargetingTurret, &Transform, Option<&SpawnOffset>, Option<&DamageKind>), Without<Enemy>>,
+    enemies: Query<&Transform, With<Enemy>>,
+    grid: Res<EnemyGrid>,
+    mut damage_events: EventWriter<DamageEvent>
+) {
+    for (
+        turret_entity,
+        mut rail_gun,
+        turret,
+        turret_transform,
+        spawn_offset,
+        damage_kind
+    ) in &mut rail_guns {
+        let Some(target_transform) = turret.target.and_then(|target| enemies.get(target).ok()) else {
+            rail_gun.charge.reset();
+            continue;
+        };
+
+        rail_gun.charge.tick(time.delta());
+        if !rail_gun.charge.finished() {
+            continue;
+        }
+
+        let to_target = (target_transform.translation - turret_transform.translation).truncate();
+        let target_angle = to_target.y.atan2(to_target.x) - std::f32::consts::FRAC_PI_2;
+        let current_angle = turret_transform.rotation.to_euler(EulerRot::XYZ).2;
+
+        if shortest_angle_diff(current_angle, target_angle).abs() > RAIL_GUN_AIM_TOLERANCE {
+            continue;
+        }
+
+        rail_gun.charge.reset();
+
+        let beam_start = (turret_transform.translation + turret_transform.rotation * spawn_offset.map_or(Vec3::ZERO, |off| off.0)).truncate();
+        let direction = (turret_transform.rotation * Vec3::Y).truncate();
+
+        let beam_center = beam_start + direction * rail_gun.max_range / 2.;
+        let mut pierced: Vec<(Entity, f32)> = grid.overlapping(beam_center, (rail_gun.max_range + rail_gun.beam_width) / 2.)
+            .filter_map(|enemy| {
+                let offset = enemy.pos - beam_start;
+                let along = offset.dot(direction);
+                let across = offset.perp_dot(direction).abs();
+
+                let on_line = (0.0..=rail_gun.max_range).contains(&along) && across <= enemy.radius + rail_gun.beam_width / 2.;
+                on_line.then_some((enemy.entity, along))
+            })
+            .collect();
+
+        pierced.sort_by(|(_, a), (_, b)| a.total_cmp(b));
+
+        let damage_type = damage_kind.map_or(DamageType::Kinetic, |kind| kind.0);
+        let mut damage = rail_gun.damage;
+
+        for (target, _) in pierced {
+            damage_events.send(DamageEvent {
+                source: Some(turret_entity),
+                target,
+                amount: damage,
+                damage_type
+            });
+
+            damage *= rail_gun.falloff;
+        }
+
+        commands.spawn((
+            StateScoped(InSession),
+            RailGunBeam,
+            Decaying {
+                decay_timer: Timer::from_seconds(RAIL_GUN_BEAM_DECAY, TimerMode::Once),
+                decay_type: DecayType::Transparency
             },
-            ..default()
+            TransformBundle::from_transform(Transform {
+                translation: (beam_start + direction * rail_gun.max_range / 2.).extend(LASER_Z),
+                rotation: turret_transform.rotation,
+                scale: Vec3::new(rail_gun.beam_width / RAIL_GUN_BEAM_SPRITE_SIZE, rail_gun.max_range / RAIL_GUN_BEAM_SPRITE_SIZE, 1.0)
+            })
+        ));
+    }
+}
+
+const LIGHTNING_ARC_DURATION: f32 = 0.2;
+
+pub fn chain_lightning_attack_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut turrets: Query<(Entity, &ChainLightningTurret, &TargetingTurret, &Transform, Option<&mut AttackDelay>, Option<&InstantDamage>, Option<&DamageKind>, Option<&OnHitEffect>)>,
+    enemies: Query<(Entity, &Transform), With<Enemy>>,
+    grid: Res<EnemyGrid>,
+    mut damage_events: EventWriter<DamageEvent>,
+    mut effect_events: EventWriter<ApplyStatusEffect>
+) {
+    for (
+        turret_entity,
+        chain_lightning,
+        turret,
+        turret_transform,
+        attack_delay,
+        damage,
+        damage_kind,
+        on_hit_effect
+    ) in &mut turrets {
+        let Some((first_target, first_transform)) = turret.target.and_then(|target| enemies.get(target).ok()) else {
+            continue;
+        };
+
+        if let Some(mut attack_delay) = attack_delay {
+            attack_delay.0.tick(time.delta());
+            if !attack_delay.0.finished() {
+                continue;
+            }
+        }
+
+        let damage_type = damage_kind.map_or(DamageType::Electric, |kind| kind.0);
+        let mut damage = damage.map_or(0., |damage| damage.0);
+        let mut target = first_target;
+        let mut from = first_transform.translation.truncate();
+        let mut hit = vec![target];
+        let mut points = vec![turret_transform.translation.truncate(), from];
+
+        loop {
+            damage_events.send(DamageEvent {
+                source: Some(turret_entity),
+                target,
+                amount: damage,
+                damage_type
+            });
+
+            if let Some(on_hit_effect) = on_hit_effect {
+                effect_events.send(ApplyStatusEffect {
+                    target,
+                    effect: on_hit_effect.0.clone(),
+                    source: Some(turret_entity)
+                });
+            }
+
+            if hit.len() > chain_lightning.jumps as usize {
+                break;
+            }
+
+            let next = grid.within(from, chain_lightning.jump_range)
+                .filter(|enemy| !hit.contains(&enemy.entity))
+                .map(|enemy| (enemy.entity, enemy.pos))
+                .min_by(|(_, a), (_, b)| a.distance(from).total_cmp(&b.distance(from)));
+
+            let Some((entity, pos)) = next else {
+                break;
+            };
+
+            target = entity;
+            from = pos;
+            hit.push(entity);
+            points.push(pos);
+            damage *= chain_lightning.falloff;
         }
-    ));
+
+        commands.spawn((
+            StateScoped(InSession),
+            LightningArc {
+                points,
+                timer: Timer::from_seconds(LIGHTNING_ARC_DURATION, TimerMode::Once)
+            }
+        ));
+    }
 }
 
-// pub fn laser_turret_attack_system(
-//     mut commands: Commands,
-//     game_textures: Res<GameTextures>,
-//     target_query: Query<&Target>,
-//     mut laser_turrets: Query<(&Turret, &mut LaserTurret, &Transform), Without<LaserBeam>>,
-//     mut laser_beams: Query<&mut Transform, With<LaserBeam>>
-// ) {
-//     let target = target_query.single();
-
-//     for (turret, mut laser_turret, turret_transform) in &mut laser_turrets {
-//         if !turret.attacking {
-//             let beam = laser_turret.laser_beam.take();
-//             if let Some(beam) = beam {
-//                 commands.entity(beam).despawn();
-//             }
-
-//             continue;
-//         }
-
-//         let target_position = target.pos;
-//         let direction = (target_position - turret_transform.translation).normalize();
-//         let angle = f32::atan2(direction.y, direction.x);
-        
-//         let target_angle = direction.y.atan2(direction.x) - std::f32::consts::FRAC_PI_2;
-//         let current_angle = turret_transform.rotation.to_euler(EulerRot::XYZ).2;
-//         let angle_diff = shortest_angle_diff(current_angle, target_angle);
-
-//         if angle_diff.abs() > ROTATION_EPSILON * 2.0 {
-//             let beam = laser_turret.laser_beam.take();
-//             if let Some(beam) = beam {
-//                 commands.entity(beam).despawn();
-//             }
-
-//             continue;
-//         }
-        
-//         let beam_start = turret_transform.translation + turret_transform.rotation * turret.spawn_offset;
-        
-//         let distance = beam_start.distance(target_position);
-//         let rotation = Quat::from_rotation_z(angle - PI / 2.0);
-
-//         let midpoint = beam_start + direction * distance / 2.0;    
-//         if let Some(exising_beam) = laser_turret.laser_beam {
-//             if let Ok(mut laser_transform) = laser_beams.get_mut(exising_beam) {
-//                 laser_transform.translation = midpoint;
-//                 laser_transform.rotation = rotation;
-//                 laser_transform.scale = Vec3::new(1.0, distance / 12.0, 1.0);
-//             }
-//         } else {
-//             let id = commands.spawn(SpriteBundle {
-//                 texture: game_textures.laser_beam.clone(),
-//                 transform: Transform {
-//                     translation: midpoint,
-//                     rotation,
-//                     scale: Vec3::new(1.0, distance / 12.0, 1.0),
-//                     ..default()
-//                 },
-//                 ..default()
-//             })
-//             .insert(LaserBeam { damage_per_second: 10. }).id();
-    
-//             laser_turret.laser_beam = Some(id);
-//         }
-//     }
-// }
+pub fn lightning_arc_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut arcs: Query<(Entity, &mut LightningArc)>
+) {
+    for (entity, mut arc) in &mut arcs {
+        arc.timer.tick(time.delta());
+        if arc.timer.finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::components::paths::LanePath;
+
+    use super::*;
+
+    struct Candidate {
+        entity: Entity,
+        spawn_id: SpawnId,
+        transform: Transform,
+        health: Health,
+        speed: MovementSpeed,
+        follower: PathFollower,
+        stealth: bool,
+        revealed: bool
+    }
+
+    fn candidate(id: u32, distance: f32, health: f32) -> Candidate {
+        Candidate {
+            entity: Entity::from_raw(id),
+            spawn_id: SpawnId(id.into()),
+            transform: Transform::from_xyz(distance, 0., 0.),
+            health: Health::new(health),
+            speed: MovementSpeed(10.),
+            follower: PathFollower { lane: 0, distance },
+            stealth: false,
+            revealed: false
+        }
+    }
+
+    fn select(candidates: &[Candidate], priority: TargetingPriority) -> Option<Entity> {
+        let paths = MapPaths { lanes: vec![LanePath::new("lane".into(), vec![Vec2::ZERO, Vec2::new(100., 0.)])] };
+        let candidates = candidates.iter().map(|candidate| (
+            candidate.entity,
+            &candidate.spawn_id,
+            &candidate.transform,
+            &candidate.health,
+            &candidate.speed,
+            &candidate.follower,
+            candidate.stealth,
+            candidate.revealed
+        ));
+
+        select_target(candidates, priority, Vec2::ZERO, &paths).map(|(entity, _)| entity)
+    }
+
+    #[test]
+    fn priorities_pick_their_favourite() {
+        let candidates = [candidate(1, 20., 5.), candidate(2, 60., 50.), candidate(3, 40., 1.)];
+
+        assert_eq!(select(&candidates, TargetingPriority::First), Some(Entity::from_raw(2)));
+        assert_eq!(select(&candidates, TargetingPriority::Last), Some(Entity::from_raw(1)));
+        assert_eq!(select(&candidates, TargetingPriority::Strongest), Some(Entity::from_raw(2)));
+        assert_eq!(select(&candidates, TargetingPriority::Weakest), Some(Entity::from_raw(3)));
+        assert_eq!(select(&candidates, TargetingPriority::Closest), Some(Entity::from_raw(1)));
+    }
+
+    #[test]
+    fn ties_go_to_the_first_spawned_in_any_order() {
+        let mut candidates = vec![candidate(4, 30., 10.), candidate(2, 30., 10.), candidate(7, 30., 10.)];
+
+        for priority in [TargetingPriority::First, TargetingPriority::Strongest, TargetingPriority::Fastest] {
+            assert_eq!(select(&candidates, priority), Some(Entity::from_raw(2)));
+            candidates.reverse();
+            assert_eq!(select(&candidates, priority), Some(Entity::from_raw(2)));
+        }
+    }
+
+    #[test]
+    fn stealthed_enemies_need_to_be_revealed() {
+        let mut hidden = candidate(1, 90., 10.);
+        hidden.stealth = true;
+        let candidates = [hidden, candidate(2, 10., 10.)];
+
+        assert_eq!(select(&candidates, TargetingPriority::First), Some(Entity::from_raw(2)));
+
+        let mut revealed = candidate(1, 90., 10.);
+        revealed.stealth = true;
+        revealed.revealed = true;
+
+        assert_eq!(select(&[revealed], TargetingPriority::First), Some(Entity::from_raw(1)));
+        assert_eq!(select(&[], TargetingPriority::First), None);
+    }
+}
@@ -0,0 +1,162 @@
+use std::time::Duration;
+
+use bevy::{ecs::query::QueryData, prelude::*};
+
+use crate::components::{economy::*, turrets::*, upgrades::*};
+
+/// Every turret stat an upgrade modifier can touch.
+#[derive(QueryData)]
+#[query_data(mutable)]
+pub struct UpgradableStats {
+    base_stats: Option<&'static mut BaseStats>,
+    attack_delay: Option<&'static mut AttackDelay>,
+    targeting: Option<&'static mut TargetingTurret>,
+    dispersion: Option<&'static mut AttackDispersion>,
+    velocity: Option<&'static mut LinearVelocity>,
+    instant_damage: Option<&'static mut InstantDamage>,
+    continous_damage: Option<&'static mut ContinousDamage>,
+    rail_gun: Option<&'static mut RailGunTurret>,
+    aoe: Option<&'static mut AoETurret>,
+    aura: Option<&'static mut BuffAura>
+}
+
+pub fn upgrade_turret_system(
+    mut commands: Commands,
+    mut events: EventReader<UpgradeTurret>,
+    mut wallet: ResMut<Wallet>,
+    current_trees: Res<CurrentUpgradeTrees>,
+    upgrade_trees: Res<Assets<UpgradeTrees>>,
+    mut turrets: Query<(&Turret, &mut TurretUpgrades, &mut Invested, UpgradableStats)>,
+    mut transactions: EventWriter<Transaction>
+) {
+    let Some(trees) = upgrade_trees.get(&current_trees.0) else {
+        return;
+    };
+
+    for event in events.read() {
+        let Ok((turret, mut upgrades, mut invested, mut stats)) = turrets.get_mut(event.turret) else {
+            continue;
+        };
+
+        let Some(tier) = trees.next_tier(&turret.0, &upgrades, event.branch) else {
+            info!("{} has no further upgrades in branch {}", turret.0, event.branch);
+            continue;
+        };
+
+        if !wallet.try_spend(tier.cost) {
+            info!("Not enough money for {} ({}/{})", tier.name, wallet.money, tier.cost);
+            continue;
+        }
+
+        invested.0 += tier.cost;
+        upgrades.advance(event.branch);
+
+        for modifier in &tier.modifiers {
+            apply_modifier(&mut commands, event.turret, &mut stats, modifier);
+        }
+
+        transactions.send(Transaction {
+            kind: TransactionKind::Upgrade(turret.0.clone()),
+            amount: -(tier.cost as i64),
+            balance: wallet.money
+        });
+    }
+}
+
+pub fn replay_upgrades_system(
+    mut commands: Commands,
+    current_trees: Res<CurrentUpgradeTrees>,
+    upgrade_trees: Res<Assets<UpgradeTrees>>,
+    mut turrets: Query<(Entity, &Turret, Option<&TurretUpgrades>, UpgradableStats), With<ReplayUpgrades>>
+) {
+    let Some(trees) = upgrade_trees.get(&current_trees.0) else {
+        return;
+    };
+
+    for (entity, turret, upgrades, mut stats) in &mut turrets {
+        commands.entity(entity).remove::<ReplayUpgrades>();
+
+        let Some(upgrades) = upgrades else {
+            continue;
+        };
+
+        for tier in trees.purchased_tiers(&turret.0, upgrades) {
+            for modifier in &tier.modifiers {
+                apply_modifier(&mut commands, entity, &mut stats, modifier);
+            }
+        }
+    }
+}
+
+/// Changes to `BaseStats` are picked up by the aura system, which recomputes the live stats.
+fn apply_modifier(commands: &mut Commands, turret: Entity, stats: &mut UpgradableStatsItem, modifier: &UpgradeModifier) {
+    match modifier {
+        UpgradeModifier::AttackDelayMultiplier(multiplier) => {
+            if let Some(base) = stats.base_stats.as_mut().and_then(|base| base.attack_delay.as_mut()) {
+                *base *= multiplier;
+            } else if let Some(attack_delay) = stats.attack_delay.as_mut() {
+                let duration = attack_delay.0.duration().as_secs_f32() * multiplier;
+                attack_delay.0.set_duration(Duration::from_secs_f32(duration));
+            }
+
+            if let Some(rail_gun) = stats.rail_gun.as_mut() {
+                let charge = rail_gun.charge.duration().as_secs_f32() * multiplier;
+                rail_gun.charge.set_duration(Duration::from_secs_f32(charge));
+            }
+        },
+        UpgradeModifier::DispersionMultiplier(multiplier) => {
+            if let Some(dispersion) = stats.dispersion.as_mut() {
+                dispersion.0 *= multiplier;
+            }
+        },
+        UpgradeModifier::TargetingRadiusBonus(bonus) => {
+            if let Some(base) = stats.base_stats.as_mut().and_then(|base| base.targeting_radius.as_mut()) {
+                *base += bonus;
+            } else if let Some(radius) = stats.targeting.as_mut().and_then(|targeting| targeting.targeting_radius.as_mut()) {
+                *radius += bonus;
+            }
+
+            if let Some(aoe) = stats.aoe.as_mut() {
+                aoe.range += bonus;
+            }
+
+            if let Some(rail_gun) = stats.rail_gun.as_mut() {
+                rail_gun.max_range += bonus;
+            }
+        },
+        UpgradeModifier::ProjectileSpeedMultiplier(multiplier) => {
+            if let Some(velocity) = stats.velocity.as_mut() {
+                velocity.0 *= multiplier;
+            }
+        },
+        UpgradeModifier::DamageMultiplier(multiplier) => {
+            if let Some(damage) = stats.instant_damage.as_mut() {
+                damage.0 *= multiplier;
+            }
+
+            if let Some(damage) = stats.continous_damage.as_mut() {
+                damage.damage_per_second *= multiplier;
+            }
+
+            if let Some(rail_gun) = stats.rail_gun.as_mut() {
+                rail_gun.damage *= multiplier;
+            }
+        },
+        UpgradeModifier::AuraRadiusBonus(bonus) => {
+            if let Some(aura) = stats.aura.as_mut() {
+                aura.radius += bonus;
+            }
+        },
+        UpgradeModifier::DetectionRadiusBonus(bonus) => {
+            if let Some(aura) = stats.aura.as_mut() {
+                aura.detection_bonus += bonus;
+            }
+        },
+        UpgradeModifier::AddHoming(homing) => {
+            commands.entity(turret).insert(homing.clone());
+        },
+        UpgradeModifier::AddExplosive(explosive) => {
+            commands.entity(turret).insert(explosive.clone());
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use bevy::prelude::*;
+
+use crate::components::{enemies::*, paths::MapPaths, waves::*};
+
+pub fn wave_spawner_system(
+    time: Res<Time>,
+    current_waves: Res<CurrentWaves>,
+    wave_sets: Res<Assets<WaveSet>>,
+    paths: Res<MapPaths>,
+    mut spawner: ResMut<WaveSpawner>,
+    mut call_events: EventReader<CallNextWave>,
+    mut spawn_events: EventWriter<SpawnEnemy>,
+    mut started_events: EventWriter<WaveStarted>,
+    mut cleared_events: EventWriter<WaveCleared>,
+    enemies: Query<(), With<Enemy>>
+) {
+    let Some(wave_set) = wave_sets.get(&current_waves.0) else {
+        return;
+    };
+
+    let called_early = call_events.read().count() > 0;
+    let spawner = &mut *spawner;
+
+    match &mut spawner.phase {
+        WavePhase::Loading => {
+            if paths.lanes.is_empty() {
+                return;
+            }
+
+            validate_lanes(wave_set, &paths);
+            spawner.phase = build_phase(wave_set, spawner.wave_index);
+        },
+        WavePhase::Build(timer) => {
+            timer.tick(time.delta());
+            if !timer.finished() && !called_early {
+                return;
+            }
+
+            let early_call_bonus = (timer.remaining_secs() * wave_set.early_call_bonus_per_second).round() as u32;
+            let Some(wave) = wave_set.waves.get(spawner.wave_index) else {
+                spawner.phase = WavePhase::Finished;
+                return;
+            };
+
+            spawner.groups = wave.groups.iter()
+                .filter_map(|group| paths.lane_index(&group.lane).map(|lane| GroupSpawner::new(group, lane)))
+                .collect();
+
+            spawner.phase = WavePhase::Spawning;
+            started_events.send(WaveStarted {
+                index: spawner.wave_index,
+                early_call_bonus
+            });
+
+            info!("Wave {} started", spawner.wave_index + 1);
+        },
+        WavePhase::Spawning => {
+            for group in &mut spawner.groups {
+                if group.remaining == 0 {
+                    continue;
+                }
+
+                group.timer.tick(time.delta());
+                if !group.timer.finished() {
+                    continue;
+                }
+
+                spawn_events.send(SpawnEnemy {
+                    enemy_type: group.enemy,
+                    lane: group.lane
+                });
+
+                group.remaining -= 1;
+                group.timer.set_duration(group.spacing);
+                group.timer.reset();
+            }
+
+            if spawner.groups.iter().all(|group| group.remaining == 0) {
+                spawner.phase = WavePhase::AwaitingClear;
+            }
+        },
+        WavePhase::AwaitingClear => {
+            if !enemies.is_empty() {
+                return;
+            }
+
+            cleared_events.send(WaveCleared { index: spawner.wave_index });
+            info!("Wave {} cleared", spawner.wave_index + 1);
+
+            spawner.wave_index += 1;
+            spawner.groups.clear();
+            spawner.phase = if spawner.wave_index < wave_set.waves.len() {
+                build_phase(wave_set, spawner.wave_index)
+            } else {
+                WavePhase::Finished
+            };
+        },
+        WavePhase::Finished => ()
+    }
+}
+
+/// Groups on lanes the map doesn't have are reported once here and never spawn.
+fn validate_lanes(wave_set: &WaveSet, paths: &MapPaths) {
+    for (wave_index, wave) in wave_set.waves.iter().enumerate() {
+        for group in &wave.groups {
+            if paths.lane_index(&group.lane).is_none() {
+                error!("Wave {} spawns {:?} on unknown lane '{}', the group is skipped", wave_index + 1, group.enemy, group.lane);
+            }
+        }
+    }
+}
+
+fn build_phase(wave_set: &WaveSet, wave_index: usize) -> WavePhase {
+    WavePhase::Build(Timer::from_seconds(wave_set.build_time(wave_index), TimerMode::Once))
+}
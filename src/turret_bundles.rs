@@ -1,41 +1,26 @@
 use bevy::prelude::*;
 
-use crate::{components::turrets::*, TurretType, ARROW_SIZE};
+use crate::{components::{damage::DamageKind, turrets::*}, TurretType};
 
 
+/// Components every turret has, the attack specific ones are inserted from its `TurretDefinition`.
 #[derive(Bundle)]
-pub struct PulseBlasterBundle {
+pub struct TurretBundle {
     pub marker: Turret,
-    pub type_marker: ProjectileTurret,
-    pub attack_dispersion: AttackDispersion,
-    pub targeting: TargetingTurret,
-    pub projectile_spawn_offset: SpawnOffset,
-    pub rotation_speed: RotationSpeed,
-    pub attack_delay: AttackDelay,
-    pub idle_rotation: IdleRotation,
-    pub sprite: SpriteBundle 
+    pub stats: TurretStats,
+    pub damage_kind: DamageKind,
+    pub base_stats: BaseStats,
+    pub spatial: TransformBundle
 }
 
-impl Default for PulseBlasterBundle {
-    fn default() -> Self {
+impl TurretBundle {
+    pub fn new(turret_type: TurretType, definition: &TurretDefinition, pos: Vec3) -> Self {
         Self {
-            marker: Turret(TurretType::PulseBlaster),
-            type_marker: ProjectileTurret,
-            attack_dispersion: AttackDispersion(std::f32::consts::PI / 16.),
-            targeting: TargetingTurret {
-                targeting_radius: Some(200.),
-                rotation: 0.,
-                has_target: false
-            },
-            projectile_spawn_offset: SpawnOffset(Vec3 { x: 0., y: ARROW_SIZE.1 / 2., z: 0. }),
-            rotation_speed: RotationSpeed(std::f32::consts::FRAC_PI_2),
-            attack_delay: AttackDelay(Timer::from_seconds(0.2, TimerMode::Repeating)),
-            idle_rotation: IdleRotation {
-                ..default()
-            },
-            sprite: SpriteBundle {
-                ..default()
-            }
+            marker: Turret(turret_type),
+            stats: TurretStats::default(),
+            damage_kind: DamageKind(definition.damage_type),
+            base_stats: definition.base_stats(),
+            spatial: TransformBundle::from_transform(Transform::from_translation(pos))
         }
     }
 }
@@ -10,20 +10,30 @@ pub struct Button {
 #[derive(Component, Debug, Default, Clone, PartialEq)]
 struct ButtonUi;
 
+#[derive(Component)]
+struct ButtonText;
+
+#[derive(Component)]
+struct ButtonImage;
+
 fn build_component(
     mut commands: Commands,
     query: Query<(Entity, &Button), Added<Button>>,
     assets: Res<AssetServer>
 ) {
     for (entity, button_source) in &query {
+        // Text-only buttons center their label instead of captioning the image
+        let text_pos = if button_source.image.is_some() { Rl((50., 120.)) } else { Rl((50., 50.)) };
+
         let mut text = commands.spawn((
             UiLink::<ButtonUi>::path("Control/Image/Text"),
-            UiLayout::window().pos(Rl((50., 120.))).anchor(Anchor::Center).pack::<Base>(),
+            UiLayout::window().pos(text_pos).anchor(Anchor::Center).pack::<Base>(),
             UiTextSize::new().size(Rh(60.0)),
             Pickable::IGNORE,
             UiAnimator::<Hover>::new().receiver(true),
             UiColor::<Base>::new(Color::BEVYPUNK_RED),
             UiColor::<Hover>::new(Color::BEVYPUNK_YELLOW),
+            ButtonText,
         ));
 
         if let Some(source_text) = &button_source.text {
@@ -57,6 +67,7 @@ fn build_component(
                     .pos2(Rl(if button_source.hover_enlarge { 105.0 } else { 100.0 }))
                     .pack::<Hover>(),
                 UiLayoutController::default(),
+                ButtonImage,
             ));
 
             if let Some(source_image) = &button_source.image {
@@ -81,11 +92,30 @@ fn build_component(
     }
 }
 
+/// Keeps the text and image in sync when the button is changed after it was built.
+fn update_content(
+    query: Query<(&Button, &Children), Changed<Button>>,
+    mut texts: Query<&mut Text, With<ButtonText>>,
+    mut images: Query<&mut Handle<Image>, With<ButtonImage>>
+) {
+    for (button, children) in &query {
+        for child in children {
+            if let (Ok(mut text), Some(source_text)) = (texts.get_mut(*child), &button.text) {
+                text.sections[0].value.clone_from(source_text);
+            }
+
+            if let (Ok(mut image), Some(source_image)) = (images.get_mut(*child), &button.image) {
+                *image = source_image.clone();
+            }
+        }
+    }
+}
+
 pub struct ButtonPlugin;
 impl Plugin for ButtonPlugin {
     fn build(&self, app: &mut App) {
         app
             .add_plugins(UiGenericPlugin::<ButtonUi>::new())
-            .add_systems(Update, build_component.before(UiSystems::Compute));
+            .add_systems(Update, (build_component, update_content).chain().before(UiSystems::Compute));
     }
 }
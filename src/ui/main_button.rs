@@ -8,6 +8,9 @@ pub struct MainButton {
 #[derive(Component, Debug, Default, Clone, PartialEq)]
 struct MainButtonUi;
 
+#[derive(Component)]
+struct MainButtonText;
+
 fn build_component(
     mut commands: Commands,
     query: Query<(Entity, &MainButton), Added<MainButton>>,
@@ -75,6 +78,8 @@ fn build_component(
                     ..default()
                 },
 
+                MainButtonText,
+
                 // Make it non-obsructable for hit checking (mouse detection)
                 Pickable::IGNORE,
 
@@ -118,11 +123,25 @@ fn build_component(
     }
 }
 
+/// Keeps the label in sync when the button text is changed after it was built.
+fn update_text(
+    query: Query<(&MainButton, &Children), Changed<MainButton>>,
+    mut texts: Query<&mut Text, With<MainButtonText>>
+) {
+    for (button, children) in &query {
+        for child in children {
+            if let Ok(mut text) = texts.get_mut(*child) {
+                text.sections[0].value.clone_from(&button.text);
+            }
+        }
+    }
+}
+
 pub struct MainButtonPlugin;
 impl Plugin for MainButtonPlugin {
     fn build(&self, app: &mut App) {
         app
             .add_plugins(UiGenericPlugin::<MainButtonUi>::new())
-            .add_systems(Update, build_component.before(UiSystems::Compute));
+            .add_systems(Update, (build_component, update_text).chain().before(UiSystems::Compute));
     }
 }
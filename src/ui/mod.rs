@@ -4,6 +4,9 @@ pub use button::*;
 pub mod main_button;
 pub use main_button::*;
 
+pub mod slider;
+pub use slider::*;
+
 use bevy::prelude::*;
 
 /// Plugin adding all our component logic
@@ -12,6 +15,7 @@ impl Plugin for ComponentPlugin {
     fn build(&self, app: &mut App) {
         app
             .add_plugins(ButtonPlugin)
-            .add_plugins(MainButtonPlugin);
+            .add_plugins(MainButtonPlugin)
+            .add_plugins(SliderPlugin);
     }
 }
\ No newline at end of file
@@ -0,0 +1,161 @@
+use crate::*;
+
+const SLIDER_STEPS: usize = 10;
+
+#[derive(Component, Debug, Default, Clone, PartialEq)]
+pub struct Slider {
+    pub text: String,
+    /// Between 0 and 1, in steps of `1 / SLIDER_STEPS`.
+    pub value: f32,
+}
+
+#[derive(Component, Debug, Default, Clone, PartialEq)]
+struct SliderUi;
+
+#[derive(Component)]
+struct SliderText;
+
+#[derive(Component)]
+struct SliderSegment {
+    slider: Entity,
+    image: Entity,
+    value: f32,
+}
+
+fn segment_color(filled: bool) -> Color {
+    if filled { Color::BEVYPUNK_RED } else { Color::BEVYPUNK_RED.with_alpha(0.15) }
+}
+
+fn slider_label(slider: &Slider) -> String {
+    format!("{}  {:.0}%", slider.text, slider.value * 100.)
+}
+
+fn build_component(
+    mut commands: Commands,
+    query: Query<(Entity, &Slider), Added<Slider>>,
+    assets: Res<AssetServer>,
+    mut materials: ResMut<Assets<ColorMaterial>>
+) {
+    for (entity, slider_source) in &query {
+        commands.entity(entity).insert(
+            UiTreeBundle::<SliderUi>::from(UiTree::new2d("Slider")),
+        ).with_children(|ui| {
+
+            ui.spawn((
+                UiLink::<SliderUi>::path("Control"),
+                UiLayout::window_full().pack::<Base>(),
+                UiMaterial2dBundle {
+                    material: materials.add(Color::BLACK.with_alpha(0.5)),
+                    ..default()
+                },
+                Pickable::IGNORE,
+            ));
+
+            ui.spawn((
+                UiLink::<SliderUi>::path("Control/Text"),
+                UiLayout::window().pos((Rh(40.0), Rl(50.0))).anchor(Anchor::CenterLeft).pack::<Base>(),
+                UiText2dBundle {
+                    text: Text::from_section(slider_label(slider_source),
+                        TextStyle {
+                            font: assets.load(AssetPath::FONT_MEDIUM),
+                            font_size: 60.0,
+                            color: Color::BEVYPUNK_RED
+                        }),
+                    ..default()
+                },
+                SliderText,
+                Pickable::IGNORE,
+            ));
+
+            ui.spawn((
+                UiLink::<SliderUi>::path("Control/Track"),
+                UiLayout::window().pos(Rl((55.0, 25.0))).size(Rl((42.0, 50.0))).pack::<Base>(),
+            ));
+
+            let width = 100.0 / SLIDER_STEPS as f32;
+            for step in 0..SLIDER_STEPS {
+                let value = (step + 1) as f32 / SLIDER_STEPS as f32;
+                let path = format!("Control/Track/Segment {step}");
+
+                let image = ui.spawn((
+                    UiLink::<SliderUi>::path(path.as_str()),
+                    UiLayout::window().x(Rl(step as f32 * width)).size(Rl((width * 0.8, 100.0))).pack::<Base>(),
+                    UiMaterial2dBundle {
+                        material: materials.add(Color::WHITE),
+                        ..default()
+                    },
+                    Pickable::IGNORE,
+                    UiAnimator::<Hover>::new().receiver(true),
+                    UiColor::<Base>::new(segment_color(value <= slider_source.value + f32::EPSILON)),
+                    UiColor::<Hover>::new(Color::BEVYPUNK_YELLOW.with_alpha(1.2)),
+                )).id();
+
+                ui.spawn((
+                    UiLink::<SliderUi>::path(format!("{path}/Zone")),
+                    UiLayout::window_full().pack::<Base>(),
+                    UiZoneBundle::default(),
+                    UiAnimator::<Hover>::new().forward_speed(5.0).backward_speed(1.0),
+                    UiAnimatorPipe::<Hover>::new(vec![image]),
+                    OnHoverSetCursor::new(CursorIcon::Pointer),
+                    UiClickEmitter::SELF,
+                    SliderSegment { slider: entity, image, value },
+                ));
+            }
+        });
+    }
+}
+
+/// Clicking a segment sets the value to it, clicking the lowest filled segment again mutes the slider.
+fn segment_clicked(
+    mut events: EventReader<UiClickEvent>,
+    segments: Query<&SliderSegment>,
+    mut sliders: Query<&mut Slider>
+) {
+    for event in events.read() {
+        let Ok(segment) = segments.get(event.target) else {
+            continue;
+        };
+
+        let Ok(mut slider) = sliders.get_mut(segment.slider) else {
+            continue;
+        };
+
+        let first_step = 1.0 / SLIDER_STEPS as f32;
+        slider.value = if (slider.value - segment.value).abs() < f32::EPSILON && segment.value <= first_step {
+            0.0
+        } else {
+            segment.value
+        };
+    }
+}
+
+/// Keeps the label and the filled segments in sync with the value.
+fn update_value(
+    mut commands: Commands,
+    query: Query<(Entity, &Slider, &Children), Changed<Slider>>,
+    segments: Query<&SliderSegment>,
+    mut texts: Query<&mut Text, With<SliderText>>
+) {
+    for (entity, slider, children) in &query {
+        for child in children {
+            if let Ok(mut text) = texts.get_mut(*child) {
+                text.sections[0].value = slider_label(slider);
+            }
+        }
+
+        for segment in segments.iter().filter(|segment| segment.slider == entity) {
+            let filled = segment.value <= slider.value + f32::EPSILON;
+            commands.entity(segment.image).insert(UiColor::<Base>::new(segment_color(filled)));
+        }
+    }
+}
+
+pub struct SliderPlugin;
+impl Plugin for SliderPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_plugins(UiGenericPlugin::<SliderUi>::new())
+            .add_systems(Update, (build_component, update_value).chain().before(UiSystems::Compute))
+            .add_systems(Update, segment_clicked.run_if(on_event::<UiClickEvent>()).before(update_value));
+    }
+}
@@ -0,0 +1,24 @@
+use bevy::prelude::*;
+
+use bevy_game::components::turrets::*;
+
+const AURA_COLOR: Color = Color::srgba(0.3, 0.9, 1.0, 0.35);
+const BUFF_LINK_COLOR: Color = Color::srgba(0.3, 0.9, 1.0, 0.6);
+
+pub fn draw_auras_system(
+    mut gizmos: Gizmos,
+    auras: Query<(&BuffAura, &Transform)>,
+    buffed: Query<(&Buffed, &Transform)>
+) {
+    for (aura, transform) in &auras {
+        gizmos.circle_2d(transform.translation.truncate(), aura.radius, AURA_COLOR);
+    }
+
+    for (buffed, transform) in &buffed {
+        for source in &buffed.sources {
+            if let Ok((_, source_transform)) = auras.get(*source) {
+                gizmos.line_2d(source_transform.translation.truncate(), transform.translation.truncate(), BUFF_LINK_COLOR);
+            }
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use bevy::{prelude::*, window::PrimaryWindow};
+use strum::IntoEnumIterator;
+
+use bevy_game::{components::{build::*, simulation::PlayerCommand, turrets::*}, states::InSession, TurretType};
+
+use super::window_to_world_coords;
+
+const GHOST_Z: f32 = 90.;
+const GHOST_ALPHA: f32 = 0.5;
+const GHOST_VALID_COLOR: Color = Color::srgba(1.0, 1.0, 1.0, GHOST_ALPHA);
+const GHOST_INVALID_COLOR: Color = Color::srgba(1.0, 0.2, 0.2, GHOST_ALPHA);
+const RANGE_VALID_COLOR: Color = Color::srgba(1.0, 1.0, 1.0, 0.4);
+const RANGE_INVALID_COLOR: Color = Color::srgba(1.0, 0.2, 0.2, 0.4);
+
+const TURRET_HOTKEYS: [KeyCode; 11] = [
+    KeyCode::Digit1, KeyCode::Digit2, KeyCode::Digit3, KeyCode::Digit4, KeyCode::Digit5, KeyCode::Digit6,
+    KeyCode::Digit7, KeyCode::Digit8, KeyCode::Digit9, KeyCode::Digit0, KeyCode::Minus
+];
+
+#[derive(Resource, Default)]
+pub struct SelectedWeapon(pub Option<TurretType>);
+
+#[derive(Component)]
+pub struct BuildGhost;
+
+/// Sent by the HUD when the playfield itself is clicked, so clicks on UI never place turrets.
+#[derive(Event)]
+pub struct ArenaClicked;
+
+pub fn select_turret_hotkey_system(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut selected: ResMut<SelectedWeapon>
+) {
+    if keys.just_pressed(KeyCode::Escape) {
+        selected.0 = None;
+        return;
+    }
+
+    for (key, turret_type) in TURRET_HOTKEYS.iter().zip(TurretType::iter()) {
+        if keys.just_pressed(*key) {
+            info!("Selected {}", turret_type);
+            selected.0 = Some(turret_type);
+        }
+    }
+}
+
+pub fn cursor_cell(windows: &Query<&Window, With<PrimaryWindow>>, grid: &BuildGrid) -> Option<IVec2> {
+    let window = windows.single();
+    let cursor_pos = window.cursor_position()?;
+
+    grid.cell_at(window_to_world_coords(cursor_pos, window.size()).truncate())
+}
+
+pub fn build_input_system(
+    windows: Query<&Window, With<PrimaryWindow>>,
+    grid: Res<BuildGrid>,
+    selected: Res<SelectedWeapon>,
+    mut commands: EventWriter<PlayerCommand>
+) {
+    let (Some(turret_type), Some(cell)) = (&selected.0, cursor_cell(&windows, &grid)) else {
+        return;
+    };
+
+    // Clicking a placed turret selects it instead
+    if grid.occupied.contains_key(&cell) {
+        return;
+    }
+
+    commands.send(PlayerCommand::Build { turret_type: turret_type.clone(), cell });
+}
+
+pub fn spawn_build_ghost(mut commands: Commands) {
+    commands.spawn((
+        StateScoped(InSession),
+        BuildGhost,
+        SpriteBundle {
+            visibility: Visibility::Hidden,
+            ..default()
+        }
+    ));
+}
+
+pub fn build_ghost_system(
+    mut gizmos: Gizmos,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    grid: Res<BuildGrid>,
+    selected: Res<SelectedWeapon>,
+    current_definitions: Res<CurrentTurretDefinitions>,
+    definitions: Res<Assets<TurretDefinitions>>,
+    mut ghosts: Query<(&mut Transform, &mut Sprite, &mut Visibility), With<BuildGhost>>
+) {
+    let Ok((mut transform, mut sprite, mut visibility)) = ghosts.get_single_mut() else {
+        return;
+    };
+
+    let definition = selected.0.as_ref()
+        .and_then(|turret_type| definitions.get(&current_definitions.0)?.get(turret_type));
+
+    let (Some(definition), Some(cell)) = (definition, cursor_cell(&windows, &grid)) else {
+        *visibility = Visibility::Hidden;
+        return;
+    };
+
+    let center = grid.cell_center(cell);
+    let valid = grid.is_buildable(cell);
+
+    transform.translation = center.extend(GHOST_Z);
+    sprite.color = if valid { GHOST_VALID_COLOR } else { GHOST_INVALID_COLOR };
+    *visibility = Visibility::Visible;
+
+    if let Some(range) = definition.range() {
+        gizmos.circle_2d(center, range, if valid { RANGE_VALID_COLOR } else { RANGE_INVALID_COLOR });
+    }
+}
+
+/// Swaps the ghost's sprite when another turret is selected, or when the ghost was just spawned.
+pub fn build_ghost_texture_system(
+    selected: Res<SelectedWeapon>,
+    asset_server: Res<AssetServer>,
+    current_definitions: Res<CurrentTurretDefinitions>,
+    definitions: Res<Assets<TurretDefinitions>>,
+    mut ghosts: Query<(Ref<BuildGhost>, &mut Handle<Image>)>
+) {
+    for (ghost, mut texture) in &mut ghosts {
+        if !selected.is_changed() && !ghost.is_added() {
+            continue;
+        }
+
+        let definition = selected.0.as_ref()
+            .and_then(|turret_type| definitions.get(&current_definitions.0)?.get(turret_type));
+
+        if let Some(definition) = definition {
+            *texture = asset_server.load(&definition.sprite);
+        }
+    }
+}
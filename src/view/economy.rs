@@ -0,0 +1,111 @@
+use bevy::{prelude::*, window::PrimaryWindow};
+
+use bevy_game::{components::{build::BuildGrid, economy::*, enemies::EnemyKilled, simulation::PlayerCommand, turrets::Turret}, states::InSession, assets::AssetPath};
+
+use super::{window_to_world_coords, TURRET_CLICK_RADIUS};
+
+const FLOATING_TEXT_Z: f32 = 400.;
+const REJECTED_COLOR: Color = Color::srgb(1.0, 0.3, 0.3);
+const INCOME_COLOR: Color = Color::srgb(1.0, 0.85, 0.2);
+
+#[derive(Component)]
+pub struct FloatingText {
+    pub timer: Timer,
+    pub rise_speed: f32
+}
+
+impl FloatingText {
+    pub fn new(seconds: f32) -> Self {
+        Self {
+            timer: Timer::from_seconds(seconds, TimerMode::Once),
+            rise_speed: 30.
+        }
+    }
+}
+
+pub fn sell_hotkey_system(
+    windows: Query<&Window, With<PrimaryWindow>>,
+    grid: Res<BuildGrid>,
+    turrets: Query<(Entity, &Transform), With<Turret>>,
+    mut commands: EventWriter<PlayerCommand>
+) {
+    let window = windows.single();
+    let Some(cursor_pos) = window.cursor_position() else {
+        return;
+    };
+
+    let pos = window_to_world_coords(cursor_pos, window.size()).truncate();
+    let hovered = turrets.iter().find(|(_, transform)| {
+        transform.translation.truncate().distance(pos) <= TURRET_CLICK_RADIUS
+    });
+
+    if let Some(cell) = hovered.and_then(|(entity, _)| grid.cell_of(entity)) {
+        commands.send(PlayerCommand::Sell { cell });
+    }
+}
+
+pub fn build_rejected_feedback_system(
+    mut commands: Commands,
+    assets: Res<AssetServer>,
+    mut events: EventReader<BuildRejected>
+) {
+    for event in events.read() {
+        let message = match &event.reason {
+            RejectReason::NotEnoughMoney { cost, available } => format!("Not enough money ({available}/{cost})"),
+            RejectReason::InvalidCell => "Can't build here".to_string(),
+            RejectReason::Unavailable => format!("{} is not available", event.turret_type)
+        };
+
+        spawn_floating_text(&mut commands, &assets, message, event.pos, REJECTED_COLOR);
+    }
+}
+
+pub fn income_feedback_system(
+    mut commands: Commands,
+    assets: Res<AssetServer>,
+    mut events: EventReader<EnemyKilled>
+) {
+    for event in events.read() {
+        if event.bounty == 0 {
+            continue;
+        }
+
+        spawn_floating_text(&mut commands, &assets, format!("+{}", event.bounty), event.pos.truncate(), INCOME_COLOR);
+    }
+}
+
+fn spawn_floating_text(commands: &mut Commands, assets: &AssetServer, message: String, pos: Vec2, color: Color) {
+    commands.spawn((
+        StateScoped(InSession),
+        FloatingText::new(1.),
+        Text2dBundle {
+            text: Text::from_section(message, TextStyle {
+                font: assets.load(AssetPath::FONT_SEMIBOLD),
+                font_size: 22.,
+                color
+            }),
+            transform: Transform::from_translation(pos.extend(FLOATING_TEXT_Z)),
+            ..default()
+        }
+    ));
+}
+
+pub fn floating_text_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut texts: Query<(Entity, &mut FloatingText, &mut Transform, &mut Text)>
+) {
+    for (entity, mut floating_text, mut transform, mut text) in &mut texts {
+        floating_text.timer.tick(time.delta());
+        transform.translation.y += floating_text.rise_speed * time.delta_seconds();
+
+        let alpha = floating_text.timer.fraction_remaining();
+        for section in &mut text.sections {
+            section.style.color.set_alpha(alpha);
+        }
+
+        if floating_text.timer.finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
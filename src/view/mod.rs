@@ -0,0 +1,103 @@
+pub mod render;
+pub use render::*;
+
+pub mod build;
+pub use build::*;
+
+pub mod economy;
+pub use economy::*;
+
+pub mod auras;
+pub use auras::*;
+
+pub mod tooltips;
+pub use tooltips::*;
+
+pub mod selection;
+pub use selection::*;
+
+pub mod paths;
+pub use paths::*;
+
+pub mod session;
+pub use session::*;
+
+pub mod turrets;
+pub use turrets::*;
+
+pub mod upgrades;
+pub use upgrades::*;
+
+pub mod waves;
+pub use waves::*;
+
+pub mod simulation;
+pub use simulation::*;
+
+use bevy::{input::common_conditions::input_just_pressed, prelude::*};
+use bevy_game::{components::waves::WaveCleared, states::*, systems::*};
+
+/// Plugin adding player input and visual feedback on top of the simulation
+pub struct GameplayViewPlugin;
+impl Plugin for GameplayViewPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<SelectedWeapon>()
+            .init_resource::<SelectedTurret>()
+            .add_event::<ArenaClicked>()
+            .configure_sets(Update, GameplaySet.run_if(in_state(GameState::InGame)))
+            .add_systems(OnEnter(GameState::Loading), reset_selection_system)
+            .add_systems(OnEnter(InSession), (spawn_tooltip, spawn_build_ghost, spawn_exit_points_system))
+            .add_systems(Update, pause_hotkey_system.run_if(input_just_pressed(KeyCode::KeyP)))
+            .add_systems(Update, (draw_paths_system, tooltip_system).in_set(GameplaySet))
+            .add_systems(Update, save_game_system.run_if(input_just_pressed(KeyCode::F5)).run_if(accepts_player_input).in_set(GameplaySet))
+            .add_systems(FixedUpdate, save_game_system.run_if(on_event::<WaveCleared>()).run_if(accepts_player_input).after(wave_spawner_system).in_set(GameplaySet))
+            .add_systems(Update, (
+                select_turret_hotkey_system,
+                select_placed_turret_system.run_if(on_event::<ArenaClicked>()),
+                build_input_system.run_if(on_event::<ArenaClicked>()),
+                build_ghost_texture_system,
+                build_ghost_system,
+                selection_highlight_system
+            ).chain().run_if(accepts_player_input).in_set(GameplaySet))
+            .add_systems(Update, (
+                sell_hotkey_system.run_if(input_just_pressed(KeyCode::KeyX)),
+                upgrade_hotkey_system,
+                call_next_wave_hotkey_system.run_if(input_just_pressed(KeyCode::KeyN)),
+                cycle_targeting_priority_system.run_if(input_just_pressed(MouseButton::Right)),
+                cycle_game_speed_hotkey_system.run_if(input_just_pressed(KeyCode::KeyF))
+            ).run_if(accepts_player_input).in_set(GameplaySet))
+            .add_systems(Update, (
+                log_transactions_system,
+                build_rejected_feedback_system,
+                income_feedback_system,
+                floating_text_system,
+                draw_auras_system,
+                draw_lightning_arcs_system
+            ).in_set(GameplaySet))
+            .add_systems(FixedFirst, restore_simulated_transforms_system)
+            .add_systems(FixedLast, record_simulated_transforms_system)
+            .add_systems(Update, interpolate_transforms_system);
+    }
+}
+
+/// Plugin attaching sprites, meshes and visibility to the simulation entities, which only carry a transform
+pub struct GameplayRenderPlugin;
+impl Plugin for GameplayRenderPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(Startup, load_game_textures)
+            .add_systems(Update, (
+                turret_sprite_system,
+                enemy_mesh_system,
+                projectile_sprite_system,
+                aoe_mesh_system
+            ))
+            .add_systems(Update, (
+                aoe_animation_visual_system,
+                decay_fade_system,
+                stealth_alpha_system,
+                status_tint_system
+            ).run_if(in_state(InSession)));
+    }
+}
@@ -0,0 +1,14 @@
+use bevy::prelude::*;
+
+use bevy_game::components::paths::MapPaths;
+
+const PATH_COLOR: Color = Color::srgba(0.5, 0.5, 0.5, 0.6);
+
+pub fn draw_paths_system(
+    mut gizmos: Gizmos,
+    paths: Res<MapPaths>
+) {
+    for lane in &paths.lanes {
+        gizmos.linestrip_2d(lane.points.iter().copied(), PATH_COLOR);
+    }
+}
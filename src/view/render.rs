@@ -0,0 +1,210 @@
+use bevy::{prelude::*, sprite::Mesh2dHandle};
+
+use bevy_game::components::{enemies::*, status_effects::*, turrets::*};
+
+const BULLET_SPRITE: &str = "bullet.png";
+const LASER_BEAM_SPRITE: &str = "laser_beam.png";
+const RAIL_GUN_BEAM_SPRITE: &str = "rail_gun_beam.png";
+const TINT_STRENGTH: f32 = 0.6;
+
+#[derive(Resource)]
+pub struct GameTextures {
+    pub bullet: Handle<Image>,
+    pub laser_beam: Handle<Image>,
+    pub rail_gun_beam: Handle<Image>,
+}
+
+pub fn load_game_textures(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>
+) {
+    commands.insert_resource(GameTextures {
+        bullet: asset_server.load(BULLET_SPRITE),
+        laser_beam: asset_server.load(LASER_BEAM_SPRITE),
+        rail_gun_beam: asset_server.load(RAIL_GUN_BEAM_SPRITE)
+    });
+}
+
+/// Gives new turrets their sprite and swaps it when the definitions file changes.
+pub fn turret_sprite_system(
+    mut commands: Commands,
+    mut events: EventReader<AssetEvent<TurretDefinitions>>,
+    asset_server: Res<AssetServer>,
+    current_definitions: Res<CurrentTurretDefinitions>,
+    definitions: Res<Assets<TurretDefinitions>>,
+    turrets: Query<(Entity, Ref<Turret>)>
+) {
+    let modified = events.read().any(|event| event.is_modified(&current_definitions.0));
+
+    let Some(definitions) = definitions.get(&current_definitions.0) else {
+        return;
+    };
+
+    for (entity, turret) in &turrets {
+        if !modified && !turret.is_added() {
+            continue;
+        }
+
+        let Some(definition) = definitions.get(&turret.0) else {
+            continue;
+        };
+
+        commands.entity(entity).insert((Sprite::default(), asset_server.load::<Image>(&definition.sprite), VisibilityBundle::default()));
+    }
+}
+
+pub fn enemy_mesh_system(
+    mut commands: Commands,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    enemies: Query<(Entity, &HitRadius, &BaseColor), Added<Enemy>>
+) {
+    for (entity, hit_radius, base_color) in &enemies {
+        commands.entity(entity).insert((
+            Mesh2dHandle(meshes.add(Circle { radius: hit_radius.0 })),
+            materials.add(base_color.0),
+            VisibilityBundle::default()
+        ));
+    }
+}
+
+pub fn projectile_sprite_system(
+    mut commands: Commands,
+    game_textures: Res<GameTextures>,
+    projectiles: Query<(Entity, Has<LaserBeam>, Has<RailGunBeam>), Or<(Added<Projectile>, Added<LaserBeam>, Added<RailGunBeam>)>>
+) {
+    for (entity, laser_beam, rail_gun_beam) in &projectiles {
+        let texture = match (laser_beam, rail_gun_beam) {
+            (true, _) => game_textures.laser_beam.clone(),
+            (_, true) => game_textures.rail_gun_beam.clone(),
+            _ => game_textures.bullet.clone()
+        };
+
+        commands.entity(entity).insert((Sprite::default(), texture, VisibilityBundle::default()));
+    }
+}
+
+pub fn aoe_mesh_system(
+    mut commands: Commands,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    animations: Query<(Entity, &AoEAnimation, Option<&Explosion>), Added<AoEAnimation>>
+) {
+    for (entity, animation, explosion) in &animations {
+        let mesh = match explosion {
+            Some(explosion) => meshes.add(Circle { radius: explosion.radius }),
+            None => meshes.add(Annulus {
+                inner_circle: Circle { radius: 0.95 },
+                outer_circle: Circle { radius: 1. }
+            })
+        };
+
+        let color = animation.color_animation.as_ref().map_or(Color::WHITE, |animation| animation.start_color);
+        commands.entity(entity).insert((Mesh2dHandle(mesh), materials.add(color), VisibilityBundle::default()));
+    }
+}
+
+pub fn aoe_animation_visual_system(
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut animations: Query<(&AoEAnimation, &mut Transform, &Handle<ColorMaterial>)>
+) {
+    for (animation, mut transform, handle) in &mut animations {
+        let t = animation.timer.fraction();
+        let t_1 = animation.timer.fraction_remaining();
+
+        if let Some(radius_animation) = &animation.radius_animation {
+            let scale = match radius_animation {
+                RadiusAnimation::FromBaseRadius { grow_speed } => 1. + grow_speed * animation.timer.elapsed_secs(),
+                RadiusAnimation::FromStartToEnd { start_radius, end_radius } => start_radius.lerp(*end_radius, t)
+            };
+
+            transform.scale = Vec3::splat(scale);
+        }
+
+        if let Some(color_animation) = &animation.color_animation {
+            if let Some(material) = materials.get_mut(handle) {
+                let start = color_animation.start_color.to_srgba();
+                let end = color_animation.end_color.to_srgba();
+
+                material.color = Color::srgba(
+                    start.red * t_1 + end.red * t,
+                    start.green * t_1 + end.green * t,
+                    start.blue * t_1 + end.blue * t,
+                    if color_animation.animate_alpha {
+                        color_animation.alpha_factor.unwrap_or(1.0) * t_1
+                    } else {
+                        material.color.alpha()
+                    }
+                );
+            }
+        }
+    }
+}
+
+pub fn decay_fade_system(
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut projectiles: Query<(&Decaying, AnyOf<(&mut Sprite, &Handle<ColorMaterial>)>)>
+) {
+    for (decaying, sprite_or_material) in &mut projectiles {
+        if decaying.decay_type != DecayType::Transparency {
+            continue;
+        }
+
+        let alpha = decaying.decay_timer.fraction_remaining();
+
+        match sprite_or_material {
+            (Some(mut sprite), None) => sprite.color.set_alpha(alpha),
+            (None, Some(handle)) => {
+                if let Some(material) = materials.get_mut(handle) {
+                    material.color.set_alpha(alpha)
+                }
+            },
+            _ => ()
+        }
+    }
+}
+
+/// Fades stealthed enemies unless an aura reveals them.
+pub fn stealth_alpha_system(
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut enemies: Query<(&mut BaseColor, &Handle<ColorMaterial>, Has<Revealed>), With<Stealth>>
+) {
+    for (mut base_color, handle, revealed) in &mut enemies {
+        let alpha = if revealed { 1. } else { STEALTH_ALPHA };
+        if base_color.0.alpha() == alpha {
+            continue;
+        }
+
+        base_color.0.set_alpha(alpha);
+
+        if let Some(material) = materials.get_mut(handle) {
+            material.color.set_alpha(alpha);
+        }
+    }
+}
+
+pub fn status_tint_system(
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    enemies: Query<(&StatusEffects, &BaseColor, &Handle<ColorMaterial>), Changed<StatusEffects>>
+) {
+    for (status_effects, base_color, handle) in &enemies {
+        let Some(material) = materials.get_mut(handle) else {
+            continue;
+        };
+
+        material.color = match status_effects.tint() {
+            Some(tint) => {
+                let base = base_color.0.to_srgba();
+                let tint = tint.to_srgba();
+
+                Color::srgba(
+                    base.red.lerp(tint.red, TINT_STRENGTH),
+                    base.green.lerp(tint.green, TINT_STRENGTH),
+                    base.blue.lerp(tint.blue, TINT_STRENGTH),
+                    base.alpha
+                )
+            },
+            None => base_color.0
+        };
+    }
+}
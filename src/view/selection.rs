@@ -0,0 +1,61 @@
+use bevy::{prelude::*, window::PrimaryWindow};
+
+use bevy_game::{components::turrets::*, systems::turret_range, ARROW_SIZE};
+
+use super::{window_to_world_coords, SelectedWeapon, TURRET_CLICK_RADIUS};
+
+const HIGHLIGHT_COLOR: Color = Color::srgb(1.0, 0.85, 0.2);
+const RANGE_COLOR: Color = Color::srgba(1.0, 0.85, 0.2, 0.4);
+
+/// Placed turret currently shown in the inspection panel.
+#[derive(Resource, Default)]
+pub struct SelectedTurret(pub Option<Entity>);
+
+pub fn reset_selection_system(
+    mut selected: ResMut<SelectedWeapon>,
+    mut selected_turret: ResMut<SelectedTurret>
+) {
+    selected.0 = None;
+    selected_turret.0 = None;
+}
+
+pub fn select_placed_turret_system(
+    windows: Query<&Window, With<PrimaryWindow>>,
+    turrets: Query<(Entity, &Transform), With<Turret>>,
+    mut selected: ResMut<SelectedTurret>
+) {
+    let window = windows.single();
+    let Some(cursor_pos) = window.cursor_position() else {
+        return;
+    };
+
+    let pos = window_to_world_coords(cursor_pos, window.size()).truncate();
+    let hovered = turrets.iter().find(|(_, transform)| {
+        transform.translation.truncate().distance(pos) <= TURRET_CLICK_RADIUS
+    });
+
+    selected.0 = hovered.map(|(entity, _)| entity);
+}
+
+pub fn selection_highlight_system(
+    mut gizmos: Gizmos,
+    mut selected: ResMut<SelectedTurret>,
+    turrets: Query<(&Transform, Option<&TargetingTurret>, Option<&AoETurret>, Option<&BuffAura>), With<Turret>>
+) {
+    let Some(entity) = selected.0 else {
+        return;
+    };
+
+    // The selected turret was sold or the session ended
+    let Ok((transform, targeting, aoe, aura)) = turrets.get(entity) else {
+        selected.0 = None;
+        return;
+    };
+
+    let center = transform.translation.truncate();
+    gizmos.rect_2d(center, 0., Vec2::from(ARROW_SIZE) * 1.2, HIGHLIGHT_COLOR);
+
+    if let Some(range) = turret_range(targeting, aoe, aura) {
+        gizmos.circle_2d(center, range, RANGE_COLOR);
+    }
+}
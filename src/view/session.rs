@@ -0,0 +1,46 @@
+use bevy::prelude::*;
+
+use bevy_game::{components::{paths::MapPaths, session::ExitPoint}, states::{GameState, InSession}};
+
+const EXIT_Z: f32 = 5.;
+const EXIT_SIZE: f32 = 48.;
+const EXIT_COLOR: Color = Color::srgba(0.9, 0.2, 0.2, 0.8);
+
+pub fn spawn_exit_points_system(
+    mut commands: Commands,
+    paths: Res<MapPaths>
+) {
+    let mut exits: Vec<Vec2> = Vec::new();
+    for end in paths.lanes.iter().filter_map(|lane| lane.points.last()) {
+        if !exits.iter().any(|exit| exit.distance(*end) < EXIT_SIZE) {
+            exits.push(*end);
+        }
+    }
+
+    for exit in exits {
+        commands.spawn((
+            StateScoped(InSession),
+            ExitPoint,
+            SpriteBundle {
+                sprite: Sprite {
+                    color: EXIT_COLOR,
+                    custom_size: Some(Vec2::splat(EXIT_SIZE)),
+                    ..default()
+                },
+                transform: Transform::from_translation(exit.extend(EXIT_Z)),
+                ..default()
+            }
+        ));
+    }
+}
+
+pub fn pause_hotkey_system(
+    state: Res<State<GameState>>,
+    mut next_state: ResMut<NextState<GameState>>
+) {
+    match state.get() {
+        GameState::InGame => next_state.set(GameState::Paused),
+        GameState::Paused => next_state.set(GameState::InGame),
+        _ => ()
+    }
+}
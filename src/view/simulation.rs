@@ -0,0 +1,54 @@
+use bevy::prelude::*;
+
+use bevy_game::components::{enemies::Enemy, simulation::*, turrets::*};
+
+pub fn cycle_game_speed_hotkey_system(
+    time: Res<Time<Virtual>>,
+    mut commands: EventWriter<PlayerCommand>
+) {
+    let current = GAME_SPEEDS.iter().position(|speed| *speed == time.relative_speed()).unwrap_or(0);
+    let speed = GAME_SPEEDS[(current + 1) % GAME_SPEEDS.len()];
+
+    commands.send(PlayerCommand::SetGameSpeed { speed });
+}
+
+/// Puts the simulated transforms back before the next fixed step.
+pub fn restore_simulated_transforms_system(mut query: Query<(&Interpolated, &mut Transform)>) {
+    for (interpolated, mut transform) in &mut query {
+        transform.translation = interpolated.current;
+        transform.rotation = interpolated.current_rotation;
+        transform.scale = interpolated.current_scale;
+    }
+}
+
+pub fn record_simulated_transforms_system(
+    mut commands: Commands,
+    added: Query<(Entity, &Transform), (Or<(With<Enemy>, With<Projectile>, With<Turret>, With<LaserBeam>)>, Without<Interpolated>)>,
+    mut query: Query<(&Transform, &mut Interpolated)>
+) {
+    for (entity, transform) in &added {
+        commands.entity(entity).insert(Interpolated::new(transform));
+    }
+
+    for (transform, mut interpolated) in &mut query {
+        interpolated.previous = interpolated.current;
+        interpolated.current = transform.translation;
+        interpolated.previous_rotation = interpolated.current_rotation;
+        interpolated.current_rotation = transform.rotation;
+        interpolated.previous_scale = interpolated.current_scale;
+        interpolated.current_scale = transform.scale;
+    }
+}
+
+pub fn interpolate_transforms_system(
+    time: Res<Time<Fixed>>,
+    mut query: Query<(&Interpolated, &mut Transform)>
+) {
+    let t = time.overstep_fraction();
+
+    for (interpolated, mut transform) in &mut query {
+        transform.translation = interpolated.previous.lerp(interpolated.current, t);
+        transform.rotation = interpolated.previous_rotation.slerp(interpolated.current_rotation, t);
+        transform.scale = interpolated.previous_scale.lerp(interpolated.current_scale, t);
+    }
+}
@@ -0,0 +1,102 @@
+use std::fmt::Write;
+
+use bevy::{prelude::*, sprite::Anchor, window::PrimaryWindow};
+use strum::IntoEnumIterator;
+
+use bevy_game::{components::{damage::*, enemies::*, turrets::Turret}, states::InSession, assets::AssetPath};
+
+use super::{window_to_world_coords, TURRET_CLICK_RADIUS};
+
+const TOOLTIP_OFFSET: Vec3 = Vec3::new(16., 16., 0.);
+const TOOLTIP_Z: f32 = 500.;
+
+#[derive(Component)]
+pub struct Tooltip;
+
+pub fn spawn_tooltip(
+    mut commands: Commands,
+    assets: Res<AssetServer>
+) {
+    commands.spawn((
+        StateScoped(InSession),
+        Tooltip,
+        Text2dBundle {
+            text: Text::from_section("", TextStyle {
+                font: assets.load(AssetPath::FONT_MEDIUM),
+                font_size: 20.,
+                color: Color::WHITE
+            }),
+            text_anchor: Anchor::BottomLeft,
+            visibility: Visibility::Hidden,
+            ..default()
+        }
+    ));
+}
+
+pub fn tooltip_system(
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut tooltips: Query<(&mut Text, &mut Transform, &mut Visibility), With<Tooltip>>,
+    enemies: Query<(&Enemy, &Transform, &HitRadius, &Health, &Armor, &Resistances), Without<Tooltip>>,
+    turrets: Query<(&Turret, &Transform, Option<&DamageKind>), (Without<Tooltip>, Without<Enemy>)>,
+    current_definitions: Res<CurrentEnemyDefinitions>,
+    definitions: Res<Assets<EnemyDefinitions>>
+) {
+    let Ok((mut text, mut transform, mut visibility)) = tooltips.get_single_mut() else {
+        return;
+    };
+
+    let window = windows.single();
+    let Some(cursor_pos) = window.cursor_position() else {
+        *visibility = Visibility::Hidden;
+        return;
+    };
+
+    let pos = window_to_world_coords(cursor_pos, window.size());
+    let mut content = String::new();
+
+    let hovered_enemy = enemies.iter().find(|(_, enemy_transform, hit_radius, ..)| {
+        enemy_transform.translation.truncate().distance(pos.truncate()) <= hit_radius.0
+    });
+
+    let hovered_turret = turrets.iter().find(|(_, turret_transform, _)| {
+        turret_transform.translation.truncate().distance(pos.truncate()) <= TURRET_CLICK_RADIUS
+    });
+
+    if let Some((enemy, _, _, health, armor, resistances)) = hovered_enemy {
+        let _ = writeln!(content, "{}", enemy.0);
+        let _ = writeln!(content, "HP {:.0}/{:.0}  Armor {:.0}", health.current.max(0.), health.max, armor.0);
+
+        for damage_type in DamageType::iter() {
+            let multiplier = resistances.multiplier(damage_type);
+            if multiplier > 1. {
+                let _ = writeln!(content, "Weak to {damage_type} x{multiplier:.2}");
+            } else if multiplier < 1. {
+                let _ = writeln!(content, "Resists {damage_type} x{multiplier:.2}");
+            }
+        }
+    } else if let Some((turret, _, damage_kind)) = hovered_turret {
+        let damage_type = damage_kind.map_or(DamageType::default(), |kind| kind.0);
+
+        let _ = writeln!(content, "{}", turret.0);
+        let _ = writeln!(content, "Damage: {damage_type}");
+
+        if let Some(definitions) = definitions.get(&current_definitions.0) {
+            for enemy_type in EnemyType::iter() {
+                let Some(definition) = definitions.get(enemy_type) else {
+                    continue;
+                };
+
+                let _ = writeln!(content, "vs {enemy_type} x{:.2}", definition.resistances.multiplier(damage_type));
+            }
+        }
+    }
+
+    if content.is_empty() {
+        *visibility = Visibility::Hidden;
+        return;
+    }
+
+    text.sections[0].value = content.trim_end().to_string();
+    transform.translation = (pos + TOOLTIP_OFFSET).with_z(TOOLTIP_Z);
+    *visibility = Visibility::Visible;
+}
@@ -0,0 +1,48 @@
+use bevy::{prelude::*, window::PrimaryWindow};
+
+use bevy_game::{components::{build::BuildGrid, simulation::PlayerCommand, turrets::*}, ARROW_SIZE};
+
+const LIGHTNING_ARC_COLOR: Color = Color::srgb(0.6, 0.8, 1.0);
+
+pub const TURRET_CLICK_RADIUS: f32 = ARROW_SIZE.0 / 2.;
+
+pub fn window_to_world_coords(cursor_pos: Vec2, window_size: Vec2) -> Vec3 {
+    Vec3 { 
+        x: cursor_pos.x - window_size.x / 2.0,
+        y: window_size.y / 2.0 - cursor_pos.y,
+        z: 0.0
+    }
+}
+
+pub fn draw_lightning_arcs_system(
+    mut gizmos: Gizmos,
+    arcs: Query<&LightningArc>
+) {
+    for arc in &arcs {
+        gizmos.linestrip_2d(arc.points.iter().copied(), LIGHTNING_ARC_COLOR.with_alpha(arc.timer.fraction_remaining()));
+    }
+}
+
+pub fn cycle_targeting_priority_system(
+    windows: Query<&Window, With<PrimaryWindow>>,
+    grid: Res<BuildGrid>,
+    turrets: Query<(Entity, &Transform), With<TargetingTurret>>,
+    mut commands: EventWriter<PlayerCommand>
+) {
+    let window = windows.single();
+    let Some(cursor_pos) = window.cursor_position() else {
+        return;
+    };
+
+    let pos = window_to_world_coords(cursor_pos, window.size()).truncate();
+
+    for (turret, transform) in &turrets {
+        if transform.translation.truncate().distance(pos) > TURRET_CLICK_RADIUS {
+            continue;
+        }
+
+        if let Some(cell) = grid.cell_of(turret) {
+            commands.send(PlayerCommand::CycleTargeting { cell });
+        }
+    }
+}
@@ -0,0 +1,35 @@
+use bevy::{prelude::*, window::PrimaryWindow};
+
+use bevy_game::components::{build::BuildGrid, simulation::PlayerCommand, upgrades::TurretUpgrades};
+
+use super::{window_to_world_coords, TURRET_CLICK_RADIUS};
+
+pub fn upgrade_hotkey_system(
+    keys: Res<ButtonInput<KeyCode>>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    grid: Res<BuildGrid>,
+    turrets: Query<(Entity, &Transform), With<TurretUpgrades>>,
+    mut commands: EventWriter<PlayerCommand>
+) {
+    let branch = if keys.just_pressed(KeyCode::KeyU) {
+        0
+    } else if keys.just_pressed(KeyCode::KeyI) {
+        1
+    } else {
+        return;
+    };
+
+    let window = windows.single();
+    let Some(cursor_pos) = window.cursor_position() else {
+        return;
+    };
+
+    let pos = window_to_world_coords(cursor_pos, window.size()).truncate();
+    let hovered = turrets.iter().find(|(_, transform)| {
+        transform.translation.truncate().distance(pos) <= TURRET_CLICK_RADIUS
+    });
+
+    if let Some(cell) = hovered.and_then(|(turret, _)| grid.cell_of(turret)) {
+        commands.send(PlayerCommand::Upgrade { cell, branch });
+    }
+}
@@ -0,0 +1,7 @@
+use bevy::prelude::*;
+
+use bevy_game::components::simulation::PlayerCommand;
+
+pub fn call_next_wave_hotkey_system(mut commands: EventWriter<PlayerCommand>) {
+    commands.send(PlayerCommand::CallNextWave);
+}
@@ -0,0 +1,112 @@
+use std::time::Duration;
+
+use bevy::{prelude::*, state::app::StatesPlugin, time::TimeUpdateStrategy};
+use bevy_prng::ChaCha8Rng;
+use bevy_rand::plugin::EntropyPlugin;
+
+use bevy_game::{
+    components::{economy::Wallet, enemies::*, paths::{CurrentMap, PathFollower}, session::Lives, simulation::*, turrets::*, upgrades::CurrentUpgradeTrees, waves::*},
+    states::GameState,
+    systems::GameplayPlugin,
+    TurretType, DEFAULT_ENEMIES, DEFAULT_MAP, DEFAULT_TURRETS, DEFAULT_UPGRADES, DEFAULT_WAVES
+};
+
+/// Updates after which a run is considered stuck.
+const MAX_UPDATES: u32 = 100_000;
+
+/// Commands of the scripted session and the ticks they are sent on.
+pub fn script() -> Vec<(u64, PlayerCommand)> {
+    vec![
+        (2, PlayerCommand::Build { turret_type: TurretType::PulseBlaster, cell: IVec2::new(5, 8) }),
+        (2, PlayerCommand::Build { turret_type: TurretType::PulseBlaster, cell: IVec2::new(14, 8) }),
+        (2, PlayerCommand::Build { turret_type: TurretType::IonCannon, cell: IVec2::new(10, 4) }),
+        (10, PlayerCommand::CallNextWave),
+        (600, PlayerCommand::Upgrade { cell: IVec2::new(5, 8), branch: 0 }),
+        (900, PlayerCommand::CycleTargeting { cell: IVec2::new(10, 4) })
+    ]
+}
+
+/// Windowless app simulating one fixed step per update, set up like a headless run.
+pub fn simulation_app(seed: u64) -> App {
+    let mut app = App::new();
+    app
+        .add_plugins(MinimalPlugins)
+        .add_plugins((AssetPlugin::default(), StatesPlugin, EntropyPlugin::<ChaCha8Rng>::default()))
+        .init_state::<GameState>()
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(1. / SIMULATION_HZ)))
+        .add_plugins(GameplayPlugin);
+
+    let asset_server = app.world().resource::<AssetServer>().clone();
+    app
+        .insert_resource(CurrentMap(asset_server.load(DEFAULT_MAP)))
+        .insert_resource(CurrentWaves(asset_server.load(DEFAULT_WAVES)))
+        .insert_resource(CurrentEnemyDefinitions(asset_server.load(DEFAULT_ENEMIES)))
+        .insert_resource(CurrentUpgradeTrees(asset_server.load(DEFAULT_UPGRADES)))
+        .insert_resource(CurrentTurretDefinitions(asset_server.load(DEFAULT_TURRETS)))
+        .insert_resource(RequestedSeed(seed));
+
+    app.world_mut().resource_mut::<NextState<GameState>>().set(GameState::Loading);
+    app
+}
+
+/// Finishes the app and updates it until the simulation reaches `tick` or the session ends.
+pub fn run_until(app: &mut App, tick: u64) {
+    app.finish();
+    app.cleanup();
+
+    for _ in 0..MAX_UPDATES {
+        app.update();
+
+        match app.world().resource::<State<GameState>>().get() {
+            GameState::MainMenu | GameState::Loading => continue,
+            GameState::GameOver | GameState::Victory => return,
+            GameState::InGame | GameState::Paused => {}
+        }
+
+        if app.world().resource::<SimulationTick>().0 >= tick {
+            return;
+        }
+    }
+
+    panic!("simulation did not reach tick {tick} after {MAX_UPDATES} updates");
+}
+
+/// Everything the outcome of a session depends on, floats compared bit for bit.
+#[derive(Debug, PartialEq)]
+pub struct Snapshot {
+    pub tick: u64,
+    pub wave_index: usize,
+    pub lives: u32,
+    pub money: u32,
+    pub enemies: Vec<(String, usize, u32, u32)>,
+    pub turrets: Vec<(String, u32, u32, u32, u32)>
+}
+
+pub fn snapshot(world: &mut World) -> Snapshot {
+    let mut enemies: Vec<_> = world.query::<(&Enemy, &PathFollower, &Health)>()
+        .iter(world)
+        .map(|(enemy, follower, health)| (enemy.0.to_string(), follower.lane, follower.distance.to_bits(), health.current.to_bits()))
+        .collect();
+    enemies.sort();
+
+    let mut turrets: Vec<_> = world.query::<(&Turret, &Transform, &TurretStats)>()
+        .iter(world)
+        .map(|(turret, transform, stats)| (
+            turret.0.to_string(),
+            transform.translation.x.to_bits(),
+            transform.translation.y.to_bits(),
+            stats.damage_dealt.to_bits(),
+            stats.kills
+        ))
+        .collect();
+    turrets.sort();
+
+    Snapshot {
+        tick: world.resource::<SimulationTick>().0,
+        wave_index: world.resource::<WaveSpawner>().wave_index,
+        lives: world.resource::<Lives>().0,
+        money: world.resource::<Wallet>().money,
+        enemies,
+        turrets
+    }
+}
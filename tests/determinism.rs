@@ -0,0 +1,42 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy_game::{
+    components::simulation::{PlayerCommand, SimulationTick},
+    states::GameplaySet,
+    systems::{dispatch_player_commands_system, tick_simulation_system}
+};
+
+/// Player commands sent on the given ticks.
+#[derive(Resource)]
+struct Script(Vec<(u64, PlayerCommand)>);
+
+fn script_system(
+    tick: Res<SimulationTick>,
+    script: Res<Script>,
+    mut commands: EventWriter<PlayerCommand>
+) {
+    commands.send_batch(script.0.iter()
+        .filter(|(at, _)| *at == tick.0)
+        .map(|(_, command)| command.clone()));
+}
+
+fn run(seed: u64) -> common::Snapshot {
+    let mut app = common::simulation_app(seed);
+    app
+        .insert_resource(Script(common::script()))
+        .add_systems(FixedUpdate, script_system
+            .after(tick_simulation_system)
+            .before(dispatch_player_commands_system)
+            .in_set(GameplaySet));
+
+    common::run_until(&mut app, 3000);
+    common::snapshot(app.world_mut())
+}
+
+#[test]
+fn same_seed_and_commands_play_out_the_same() {
+    let first = run(7);
+    assert_eq!(first.turrets.len(), 3, "the scripted turrets should have been built");
+    assert_eq!(first, run(7));
+}
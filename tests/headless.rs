@@ -0,0 +1,48 @@
+use std::process::{Command, Output};
+
+use serde_json::Value;
+
+/// Runs the headless binary, which has to build without the `render` feature.
+fn headless(args: &[&str]) -> (Output, Value) {
+    let output = Command::new(env!("CARGO_BIN_EXE_headless"))
+        .args(args)
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .env("BEVY_ASSET_ROOT", env!("CARGO_MANIFEST_DIR"))
+        .output()
+        .expect("headless binary should start");
+
+    let summary = serde_json::from_slice(&output.stdout).unwrap_or_else(|err| {
+        panic!("stdout should be a JSON summary ({err}):\n{}", String::from_utf8_lossy(&output.stdout))
+    });
+
+    (output, summary)
+}
+
+#[test]
+fn runs_until_the_tick_limit() {
+    let (output, summary) = headless(&["--seed", "7", "--max-ticks", "600"]);
+
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(summary["outcome"], "Timeout");
+    assert_eq!(summary["seed"], 7);
+    assert_eq!(summary["ticks"], 600);
+}
+
+#[test]
+fn missing_assets_fail_the_run() {
+    let (output, summary) = headless(&["--map", "maps/missing.map.ron", "--max-ticks", "600"]);
+
+    assert!(!output.status.success());
+    assert_eq!(summary["outcome"], "Error");
+}
+
+#[test]
+fn invalid_arguments_are_rejected() {
+    let output = Command::new(env!("CARGO_BIN_EXE_headless"))
+        .args(["--seed", "not a number"])
+        .output()
+        .expect("headless binary should start");
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("usage: headless"));
+}
@@ -0,0 +1,49 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy_game::{
+    components::replay::{RecordedCommand, Replay, ReplayPlayback, REPLAY_VERSION},
+    systems::ReplayPlugin,
+    DEFAULT_MAP, DEFAULT_WAVES
+};
+
+const END: u64 = 3000;
+
+/// Stands in for the render plugin, which moves new entities to another archetype on the next frame.
+#[derive(Component)]
+struct Drawn;
+
+fn draw_system(mut commands: Commands, query: Query<Entity, (With<Transform>, Without<Drawn>)>) {
+    for entity in &query {
+        commands.entity(entity).insert(Drawn);
+    }
+}
+
+fn play_back(speed: f32) -> common::Snapshot {
+    let replay = Replay {
+        version: REPLAY_VERSION,
+        seed: 7,
+        map: DEFAULT_MAP.to_string(),
+        waves: DEFAULT_WAVES.to_string(),
+        commands: common::script().into_iter().map(|(tick, command)| RecordedCommand { tick, command }).collect(),
+        wave_starts: Vec::new(),
+        end: END
+    };
+
+    let mut app = common::simulation_app(replay.seed);
+    app
+        .insert_resource(ReplayPlayback { speed, ..ReplayPlayback::new(replay) })
+        .add_plugins(ReplayPlugin)
+        .add_systems(Update, draw_system);
+
+    common::run_until(&mut app, END);
+    common::snapshot(app.world_mut())
+}
+
+#[test]
+fn playback_speed_does_not_change_the_outcome() {
+    let normal = play_back(1.);
+    assert_eq!(normal.tick, END, "playback should stop at the end of the recording");
+    assert_eq!(normal.turrets.len(), 3, "the recorded turrets should have been built");
+    assert_eq!(normal, play_back(4.));
+}